@@ -0,0 +1,79 @@
+// benches/deposits.rs
+// Mesure de la recherche du gisement le plus proche sur de grandes cartes, en parcourant la
+// liste des gisements puis avec l'index spatial, et de celle du gisement le moins coûteux à
+// atteindre par un chemin. Lancement : cargo bench --bench deposits
+use erea::bucket_grid::manhattan;
+use erea::resources::ENERGY;
+use erea::{empty_map, Map};
+use std::time::{Duration, Instant};
+
+// Nombre de positions de robots interrogées à chaque mesure
+const ROBOTS: usize = 200;
+
+// Fonction pour mesurer la durée moyenne d'une requête
+fn measure(runs: usize, mut query: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..runs {
+        query();
+    }
+    start.elapsed() / runs as u32
+}
+
+fn main() {
+    for (size, deposits) in [(256, 500), (1000, 5000), (2000, 20000)] {
+        // Gisements et robots répartis de façon déterministe sur toute la carte
+        let mut map = empty_map(size, size);
+        let mut seed = 12345u64;
+        let mut next = move || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) as usize % size
+        };
+        let start = Instant::now();
+        for _ in 0..deposits {
+            let cell = (next(), next());
+            map.add_deposit(ENERGY, cell);
+        }
+        let build = start.elapsed();
+        let robots: Vec<(usize, usize)> = (0..ROBOTS).map(|_| (next(), next())).collect();
+
+        let scan = |from: (usize, usize)| {
+            map.deposits(ENERGY)
+                .iter()
+                .copied()
+                .min_by_key(|&pos| (manhattan(from, pos), pos.1, pos.0))
+        };
+        for &from in &robots {
+            assert_eq!(map.nearest_deposit(ENERGY, from, |_| true), scan(from));
+        }
+
+        let nearest_scan = measure(10, || {
+            for &from in &robots {
+                std::hint::black_box(scan(from));
+            }
+        });
+        let nearest_index = measure(100, || {
+            for &from in &robots {
+                std::hint::black_box(map.nearest_deposit(ENERGY, from, |_| true));
+            }
+        });
+        let by_path = measure(10, || {
+            for &from in &robots {
+                std::hint::black_box(map.nearest_deposit_by_path(
+                    ENERGY,
+                    from,
+                    Map::known_cost,
+                    usize::MAX,
+                    |_| true,
+                ));
+            }
+        });
+        let count = map.deposits(ENERGY).len();
+        println!(
+            "carte {size}x{size}, {count} gisements (ajoutés en {build:?}), {ROBOTS} robots :"
+        );
+        println!("  gisement le plus proche : {nearest_scan:?} en parcourant la liste, {nearest_index:?} avec l'index");
+        println!("  gisement le moins coûteux par le chemin : {by_path:?}");
+    }
+}
@@ -0,0 +1,80 @@
+// benches/exploration.rs
+// Mesure des requêtes de l'exploration sur de grandes cartes, en parcourant toute la carte
+// puis avec l'index des frontières : case inexplorée la plus proche de chaque robot et carte
+// entièrement explorée. Lancement : cargo bench --bench exploration
+use erea::behavior::Knowledge;
+use erea::clock::Clock;
+use erea::frontier::FrontierIndex;
+use erea::{empty_map, reveal_around};
+use std::time::{Duration, Instant};
+
+// Nombre de robots qui cherchent une case à explorer à chaque tour
+const ROBOTS: usize = 16;
+
+// Fonction pour mesurer la durée moyenne d'une requête
+fn measure(runs: usize, mut query: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..runs {
+        query();
+    }
+    start.elapsed() / runs as u32
+}
+
+fn main() {
+    let clock = Clock::default();
+    for size in [256, 1000, 2000] {
+        // Une zone explorée autour de la base et quelques percées, comme après un début de
+        // partie
+        let mut map = empty_map(size, size);
+        let base = map.base;
+        reveal_around(&mut map, base, size / 10);
+        let robots: Vec<(usize, usize)> = (0..ROBOTS)
+            .map(|i| (base.0 + i * size / 40, base.1 - i * size / 50))
+            .map(|(x, y)| (x.min(size - 1), y))
+            .collect();
+        for &position in &robots {
+            reveal_around(&mut map, position, 6);
+        }
+
+        let start = Instant::now();
+        let index = FrontierIndex::new(&map);
+        let build = start.elapsed();
+        let scan = Knowledge::new(&map, &clock);
+        let indexed = Knowledge::new(&map, &clock).with_frontier(&index);
+        for &position in &robots {
+            assert_eq!(
+                scan.nearest_unexplored(position),
+                indexed.nearest_unexplored(position)
+            );
+        }
+
+        let runs = if size > 1000 { 3 } else { 10 };
+        let nearest_scan = measure(runs, || {
+            for &position in &robots {
+                std::hint::black_box(scan.nearest_unexplored(position));
+            }
+        });
+        let nearest_index = measure(runs * 100, || {
+            for &position in &robots {
+                std::hint::black_box(indexed.nearest_unexplored(position));
+            }
+        });
+        println!("carte {size}x{size} (index construit en {build:?}), {ROBOTS} robots :");
+        println!("  case inexplorée la plus proche : {nearest_scan:?} en parcourant la carte, {nearest_index:?} avec l'index");
+
+        // Le parcours s'arrête à la première case inexplorée : le pire cas est une carte
+        // presque entièrement explorée
+        map.explore_all();
+        map.set_explored((size - 1, size - 1), false);
+        let index = FrontierIndex::new(&map);
+        let scan = Knowledge::new(&map, &clock);
+        let indexed = Knowledge::new(&map, &clock).with_frontier(&index);
+        let explored_scan = measure(runs * 100, || {
+            std::hint::black_box(scan.fully_explored());
+        });
+        let explored_index = measure(runs * 100, || {
+            std::hint::black_box(indexed.fully_explored());
+        });
+        println!("  carte entièrement explorée : {explored_scan:?} en parcourant la carte, {explored_index:?} avec l'index");
+    }
+}
@@ -0,0 +1,66 @@
+// benches/tick.rs
+// Mesure de la durée d'un tour de simulation sur de grandes cartes : le premier tour construit
+// les structures de la base, les suivants ne doivent plus dépendre de la taille de la carte.
+// Les fonctions d'exploration d'un robot seul, qui passent par les mêmes structures, sont
+// mesurées ensuite. Lancement : cargo bench --bench tick
+use erea::base::Base;
+use erea::clock::Clock;
+use erea::hpa::{Hierarchy, HIERARCHY_MIN_SIZE};
+use erea::resources::ResourceRegistry;
+use erea::{explore_map, generate_map_seeded, is_map_fully_explored, simulate_tick, Robot, Task};
+use std::time::Instant;
+
+// Nombre d'éclaireurs et de collecteurs par type de ressource
+const SCOUTS: usize = 8;
+const COLLECTORS: usize = 4;
+
+// Nombre de tours mesurés après le premier
+const TICKS: usize = 200;
+
+fn main() {
+    for size in [256, 1000, 2000] {
+        let mut map = generate_map_seeded(size, size, ResourceRegistry::standard(), 7);
+        let (x, y) = map.base;
+        let mut robots: Vec<Robot> = (0..SCOUTS)
+            .map(|_| Robot::new(x, y, Task::Explore))
+            .collect();
+        for kind in map.resources.kinds() {
+            robots.extend((0..COLLECTORS).map(|_| Robot::new(x, y, Task::Collect(kind))));
+        }
+        let mut base = Base::new();
+        if size >= HIERARCHY_MIN_SIZE {
+            base.hierarchy = Some(Hierarchy::new(&map));
+        }
+        let mut clock = Clock::default();
+
+        let start = Instant::now();
+        simulate_tick(&mut map, &mut robots, &mut base, &mut clock);
+        let first = start.elapsed();
+        let start = Instant::now();
+        for _ in 0..TICKS {
+            simulate_tick(&mut map, &mut robots, &mut base, &mut clock);
+        }
+        let tick = start.elapsed() / TICKS as u32;
+
+        let mut scout = Robot::new(x, y, Task::Explore);
+        let start = Instant::now();
+        for _ in 0..TICKS {
+            explore_map(&mut scout, &mut map, &mut base, &clock);
+        }
+        let explore = start.elapsed() / TICKS as u32;
+        let start = Instant::now();
+        for _ in 0..TICKS {
+            std::hint::black_box(is_map_fully_explored(&map, &base));
+        }
+        let explored = start.elapsed() / TICKS as u32;
+        println!("carte {size}x{size}, {} robots :", robots.len());
+        println!("  premier tour {first:?}, puis {tick:?} par tour en moyenne");
+        println!(
+            "  exploration d'un robot seul : {explore:?} par pas, carte entièrement explorée : {explored:?}"
+        );
+        println!(
+            "  reconstructions complètes : {} champ de distances, {} index des frontières",
+            base.home.rebuilds, base.frontier.rebuilds
+        );
+    }
+}
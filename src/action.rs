@@ -0,0 +1,237 @@
+// src/action.rs
+use crate::base::Base;
+use crate::traffic::Traffic;
+use crate::{Map, Robot, RobotState, Task};
+use std::error::Error;
+use std::fmt;
+
+// Direction d'un déplacement vers l'une des huit cases voisines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    // Fonction pour obtenir le décalage (dx, dy) d'un pas dans cette direction
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::NorthEast => (1, -1),
+            Direction::East => (1, 0),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (0, 1),
+            Direction::SouthWest => (-1, 1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (-1, -1),
+        }
+    }
+
+    // Fonction pour trouver la direction qui mène d'une case à une case voisine
+    pub fn towards(from: (usize, usize), to: (usize, usize)) -> Option<Direction> {
+        let offset = (
+            to.0 as isize - from.0 as isize,
+            to.1 as isize - from.1 as isize,
+        );
+        Direction::ALL
+            .into_iter()
+            .find(|direction| direction.offset() == offset)
+    }
+
+    // Fonction pour obtenir la case atteinte par un pas depuis une case, None hors de la carte
+    pub fn step(self, map: &Map, from: (usize, usize)) -> Option<(usize, usize)> {
+        let (dx, dy) = self.offset();
+        let x = from.0.checked_add_signed(dx)?;
+        let y = from.1.checked_add_signed(dy)?;
+        (x < map.width && y < map.height).then_some((x, y))
+    }
+
+    // Fonction pour obtenir le nom d'une direction, tel qu'écrit dans les scripts
+    pub fn name(self) -> &'static str {
+        match self {
+            Direction::North => "north",
+            Direction::NorthEast => "northeast",
+            Direction::East => "east",
+            Direction::SouthEast => "southeast",
+            Direction::South => "south",
+            Direction::SouthWest => "southwest",
+            Direction::West => "west",
+            Direction::NorthWest => "northwest",
+        }
+    }
+
+    // Fonction pour retrouver une direction par son nom
+    pub fn from_name(name: &str) -> Option<Direction> {
+        Direction::ALL
+            .into_iter()
+            .find(|direction| direction.name() == name)
+    }
+}
+
+// Action élémentaire d'un robot pour un tour ; seule la simulation l'applique
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Move(Direction), // avancer d'une case, la batterie payant le coût du terrain
+    Mine,            // forer ou analyser un tour le gisement sous le robot
+    Unload,          // décharger la cargaison dans le stock de la base
+    Recharge,        // recharger la batterie à la base
+    Wait,            // rester sur place
+}
+
+// Raison pour laquelle une action est refusée
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionError {
+    OutOfBounds,                                    // le déplacement sort de la carte
+    Obstacle((usize, usize)),                       // la case visée est infranchissable
+    LowBattery { needed: usize, available: usize }, // la batterie ne couvre pas le déplacement
+    NoTool,                   // le robot n'a pas l'outil ou la soute qu'exige sa tâche
+    NoDeposit,                // aucun gisement de la ressource de la tâche sous le robot
+    CargoFull,                // la soute est pleine
+    NotAtBase,                // l'action n'est possible qu'à la base
+    Occupied((usize, usize)), // la case visée est occupée ou réservée par un autre robot
+}
+
+impl fmt::Display for ActionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ActionError::OutOfBounds => write!(f, "déplacement hors de la carte"),
+            ActionError::Obstacle((x, y)) => write!(f, "obstacle en ({}, {})", x, y),
+            ActionError::LowBattery { needed, available } => write!(
+                f,
+                "batterie insuffisante ({} nécessaire, {} disponible)",
+                needed, available
+            ),
+            ActionError::NoTool => write!(f, "outil ou soute manquant"),
+            ActionError::NoDeposit => write!(f, "aucun gisement ici"),
+            ActionError::CargoFull => write!(f, "soute pleine"),
+            ActionError::NotAtBase => write!(f, "hors de la base"),
+            ActionError::Occupied((x, y)) => write!(f, "case ({}, {}) occupée", x, y),
+        }
+    }
+}
+
+impl Error for ActionError {}
+
+// Fonction pour vérifier qu'un robot peut effectuer une action, sans rien modifier. Un
+// déplacement doit rester sur la carte, éviter les obstacles, être couvert par la batterie et
+// viser une case que la règle de circulation laisse libre.
+pub fn validate(
+    map: &Map,
+    traffic: &Traffic,
+    robot: &Robot,
+    action: Action,
+) -> Result<(), ActionError> {
+    let position = (robot.x, robot.y);
+    match action {
+        Action::Move(direction) => {
+            let cell = direction
+                .step(map, position)
+                .ok_or(ActionError::OutOfBounds)?;
+            let needed = map.movement_cost(cell).ok_or(ActionError::Obstacle(cell))?;
+            if needed > robot.battery {
+                return Err(ActionError::LowBattery {
+                    needed,
+                    available: robot.battery,
+                });
+            }
+            traffic.check(map, cell)
+        }
+        Action::Mine => {
+            let Task::Collect(kind) = robot.task else {
+                return Err(ActionError::NoTool);
+            };
+            if !robot.can_collect(map) {
+                return Err(ActionError::NoTool);
+            }
+            if map.deposit_at(position) != Some(kind) {
+                return Err(ActionError::NoDeposit);
+            }
+            if robot.cargo() >= robot.modules.cargo_capacity() {
+                return Err(ActionError::CargoFull);
+            }
+            Ok(())
+        }
+        Action::Unload | Action::Recharge if position != map.base => Err(ActionError::NotAtBase),
+        Action::Unload | Action::Recharge | Action::Wait => Ok(()),
+    }
+}
+
+// Fonction pour appliquer l'action d'un robot après l'avoir validée, selon la règle de
+// circulation de la base. Une action refusée ne modifie rien et renvoie la raison du refus.
+pub fn apply(
+    map: &mut Map,
+    robot: &mut Robot,
+    base: &mut Base,
+    action: Action,
+) -> Result<(), ActionError> {
+    match action {
+        Action::Move(direction) => {
+            let from = (robot.x, robot.y);
+            let battery = robot.battery;
+            apply_move(map, &base.traffic, robot, direction)?;
+            let to = (robot.x, robot.y);
+            base.traffic.moved(map, from, to);
+            // Les déplacements des collecteurs hors exploration comptent dans leurs voyages
+            if matches!(robot.task, Task::Collect(_)) && robot.state != RobotState::Exploring {
+                base.trips.spent += battery - robot.battery;
+            }
+            Ok(())
+        }
+        Action::Mine => apply_mine(map, robot),
+        Action::Unload => {
+            validate(map, &base.traffic, robot, action)?;
+            base.unload(robot);
+            Ok(())
+        }
+        Action::Recharge => {
+            validate(map, &base.traffic, robot, action)?;
+            robot.battery = robot.modules.battery_capacity();
+            Ok(())
+        }
+        Action::Wait => Ok(()),
+    }
+}
+
+// Fonction pour déplacer un robot d'une case, en payant le coût du terrain
+pub(crate) fn apply_move(
+    map: &Map,
+    traffic: &Traffic,
+    robot: &mut Robot,
+    direction: Direction,
+) -> Result<(), ActionError> {
+    validate(map, traffic, robot, Action::Move(direction))?;
+    let (x, y) = direction.step(map, (robot.x, robot.y)).unwrap();
+    robot.battery -= map.movement_cost((x, y)).unwrap();
+    robot.x = x;
+    robot.y = y;
+    Ok(())
+}
+
+// Fonction pour forer ou analyser un tour le gisement sous un robot
+pub(crate) fn apply_mine(map: &mut Map, robot: &mut Robot) -> Result<(), ActionError> {
+    validate(map, &Traffic::default(), robot, Action::Mine)?;
+    if let Task::Collect(kind) = robot.task {
+        if robot.drill(map) {
+            *robot.load.entry(kind).or_insert(0) += 1;
+            map.deplete(kind, (robot.x, robot.y));
+        }
+    }
+    Ok(())
+}
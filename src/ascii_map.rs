@@ -0,0 +1,114 @@
+// src/ascii_map.rs
+use crate::resources::ResourceRegistry;
+use crate::terrain::Terrain;
+use crate::{empty_map_with, Map};
+use std::io::{self, Error, ErrorKind};
+
+// Format ASCII d'une carte, un caractère par case et une ligne par rangée :
+//
+//   '.' plaine            '#' roche (obstacle)
+//   ':' sable             '^' terrain accidenté
+//   'B' base
+//
+// Les gisements utilisent le symbole de leur type dans le registre ('E' énergie,
+// 'M' minerais, 'S' site scientifique pour le registre standard). Un gisement posé
+// sur un obstacle s'écrit en minuscule ('e', 'm' ou 's'). Une case ne porte qu'un seul gisement ; les cases
+// des gisements et de la base sont lues en plaine.
+
+// Fonction pour lire une carte au format ASCII
+pub fn parse_ascii_map(text: &str) -> io::Result<Map> {
+    parse_ascii_map_with(text, &ResourceRegistry::standard())
+}
+
+// Fonction pour lire une carte au format ASCII avec un registre de ressources donné
+pub fn parse_ascii_map_with(text: &str, resources: &ResourceRegistry) -> io::Result<Map> {
+    let rows: Vec<&str> = text
+        .lines()
+        .map(|line| line.trim_end())
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    let height = rows.len();
+    let width = rows.first().map_or(0, |row| row.chars().count());
+    if width == 0 {
+        return Err(Error::new(ErrorKind::InvalidData, "carte vide"));
+    }
+
+    let mut map = empty_map_with(width, height, resources.clone());
+    let mut base = None;
+
+    for (y, row) in rows.iter().enumerate() {
+        if row.chars().count() != width {
+            return Err(invalid(
+                y + 1,
+                "toutes les lignes doivent avoir la même largeur",
+            ));
+        }
+        for (x, symbol) in row.chars().enumerate() {
+            match symbol {
+                '.' => {}
+                '#' => map.set_terrain((x, y), Terrain::Rock),
+                ':' => map.set_terrain((x, y), Terrain::Sand),
+                '^' => map.set_terrain((x, y), Terrain::Rough),
+                'B' => {
+                    if base.is_some() {
+                        return Err(invalid(y + 1, "une seule base est autorisée"));
+                    }
+                    base = Some((x, y));
+                }
+                other => match resources.by_symbol(other) {
+                    Some(kind) => {
+                        if other.is_lowercase() {
+                            map.set_terrain((x, y), Terrain::Rock);
+                        }
+                        map.add_deposit(kind, (x, y));
+                    }
+                    None => {
+                        return Err(invalid(y + 1, &format!("caractère inconnu '{}'", other)));
+                    }
+                },
+            }
+        }
+    }
+
+    map.base = base.ok_or_else(|| Error::new(ErrorKind::InvalidData, "base manquante"))?;
+    Ok(map)
+}
+
+// Fonction pour écrire une carte au format ASCII
+pub fn map_to_ascii(map: &Map) -> String {
+    let mut text = String::with_capacity((map.width + 1) * map.height);
+    for y in 0..map.height {
+        for x in 0..map.width {
+            let obstacle = map.is_obstacle((x, y));
+            let symbol = if (x, y) == map.base {
+                'B'
+            } else if let Some(kind) = map.deposit_at((x, y)) {
+                let symbol = map.resources.get(kind).symbol;
+                if obstacle {
+                    symbol.to_ascii_lowercase()
+                } else {
+                    symbol
+                }
+            } else if obstacle {
+                '#'
+            } else {
+                match map.terrain_at((x, y)) {
+                    Terrain::Sand => ':',
+                    Terrain::Rough => '^',
+                    _ => '.',
+                }
+            };
+            text.push(symbol);
+        }
+        text.push('\n');
+    }
+    text
+}
+
+fn invalid(line: usize, message: &str) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!("ligne {} : {}", line, message),
+    )
+}
@@ -0,0 +1,274 @@
+// src/auction.rs
+use crate::base::Base;
+use crate::distance_field::DistanceField;
+use crate::frontier::FrontierIndex;
+use crate::resources::ResourceKind;
+use crate::scheduler::{can_perform, Policy};
+use crate::{Map, Robot, Task, BATTERY_RESERVE};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+// Ecart minimal entre deux cases frontières mises aux enchères
+pub const FRONTIER_SPACING: usize = 5;
+
+// Lot mis aux enchères par la base
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Offer {
+    Frontier((usize, usize)), // explorer une case inconnue au bord de la carte connue
+    Deposit(ResourceKind, (usize, usize)), // collecter un gisement connu
+    Delivery(usize),          // rapporter la cargaison d'un robot plein
+}
+
+impl Offer {
+    // Fonction pour obtenir la tâche qui correspond à un lot
+    pub fn task(self, robot: &Robot) -> Task {
+        match self {
+            Offer::Frontier(_) => Task::Explore,
+            Offer::Deposit(kind, _) => Task::Collect(kind),
+            Offer::Delivery(_) => robot.task,
+        }
+    }
+
+    // Fonction pour obtenir la case que vise le gagnant d'un lot
+    pub fn target(self, map: &Map) -> (usize, usize) {
+        match self {
+            Offer::Frontier(cell) | Offer::Deposit(_, cell) => cell,
+            Offer::Delivery(_) => map.base,
+        }
+    }
+}
+
+// Enchère d'un robot sur un lot : plus le coût est bas, mieux le robot est placé
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bid {
+    pub robot: usize, // indice du robot
+    pub offer: Offer, // lot visé
+    pub cost: usize,  // coût annoncé
+}
+
+fn distance(a: (usize, usize), b: (usize, usize)) -> usize {
+    a.0.abs_diff(b.0).max(a.1.abs_diff(b.1))
+}
+
+// Fonction pour lister les cases frontières de l'index, espacées d'au moins FRONTIER_SPACING
+// cases, les plus proches de la base d'abord (la première dans l'ordre des lignes à égalité)
+pub fn frontiers(map: &Map, index: &FrontierIndex, limit: usize) -> Vec<(usize, usize)> {
+    let mut cells: Vec<(usize, usize)> = index.cells().collect();
+    cells.sort_by_key(|&(x, y)| (distance((x, y), map.base), y, x));
+    let mut chosen: Vec<(usize, usize)> = Vec::new();
+    for cell in cells {
+        if chosen.len() >= limit {
+            break;
+        }
+        if chosen
+            .iter()
+            .all(|&other| distance(cell, other) >= FRONTIER_SPACING)
+        {
+            chosen.push(cell);
+        }
+    }
+    chosen
+}
+
+// Fonction pour préparer les lots d'une enchère : les frontières, les gisements connus les
+// plus proches de la base et les livraisons des robots à la soute pleine. Chaque robot ne
+// remporte qu'un lot : il y a au plus autant de frontières et de gisements de chaque type que
+// de robots. Les frontières viennent de l'index de la base, reconstruit pour l'occasion s'il
+// ne porte pas sur cette carte, et les gisements de l'index de leur ressource.
+pub fn offers(map: &Map, robots: &[Robot], base: &Base) -> Vec<Offer> {
+    let rebuilt;
+    let index = if base.frontier.fits(map) {
+        &base.frontier
+    } else {
+        rebuilt = FrontierIndex::new(map);
+        &rebuilt
+    };
+    let mut offers: Vec<Offer> = frontiers(map, index, robots.len())
+        .into_iter()
+        .map(Offer::Frontier)
+        .collect();
+    for kind in map.resources.kinds() {
+        let mut lots: Vec<(usize, usize)> = Vec::new();
+        while lots.len() < robots.len() {
+            let accept = |cell| map.is_explored(cell) && !lots.contains(&cell);
+            match map.nearest_deposit(kind, map.base, accept) {
+                Some(cell) => lots.push(cell),
+                None => break,
+            }
+        }
+        offers.extend(lots.into_iter().map(|cell| Offer::Deposit(kind, cell)));
+    }
+    offers.extend(
+        robots
+            .iter()
+            .enumerate()
+            .filter(|(_, robot)| {
+                robot.cargo() > 0 && robot.cargo() >= robot.modules.cargo_capacity()
+            })
+            .map(|(index, _)| Offer::Delivery(index)),
+    );
+    offers
+}
+
+// Fonction pour calculer le coût du chemin connu d'une position jusqu'à chacune des cases
+// visées (triées), sans dépasser `limit` : la recherche (Dijkstra) s'arrête quand toutes sont
+// atteintes. Les cases hors de portée n'ont pas de coût.
+fn travel_costs(
+    map: &Map,
+    from: (usize, usize),
+    targets: &[(usize, usize)],
+    limit: usize,
+) -> HashMap<(usize, usize), usize> {
+    let mut costs = HashMap::new();
+    let mut found = HashMap::new();
+    let mut open = BinaryHeap::new();
+    costs.insert(from, 0);
+    open.push(Reverse((0, from)));
+    while let Some(Reverse((cost, cell))) = open.pop() {
+        if costs.get(&cell).is_some_and(|&known| cost > known) {
+            continue;
+        }
+        if targets.binary_search(&cell).is_ok() {
+            found.insert(cell, cost);
+            if found.len() == targets.len() {
+                break;
+            }
+        }
+        for next in map.neighbors(cell) {
+            let Some(step) = map.known_cost(next) else {
+                continue;
+            };
+            if cost + step <= limit && costs.get(&next).is_none_or(|&known| cost + step < known) {
+                costs.insert(next, cost + step);
+                open.push(Reverse((cost + step, next)));
+            }
+        }
+    }
+    found
+}
+
+// Fonction pour calculer l'enchère d'un robot sur un lot, None s'il ne peut pas l'honorer.
+// Le coût compte le chemin jusqu'au lot, donné par `travel` ; pour un gisement, le retour à
+// la base, lu dans le champ de distances `home`, est réparti sur la capacité de la soute et
+// le stock de la ressource renchérit le lot. Un robot dont la batterie ne couvre pas le
+// chemin jusqu'au lot puis le retour à la base, plus la réserve, ne se porte pas candidat,
+// pas plus que sur un gisement revendiqué par un autre robot.
+pub fn bid(
+    map: &Map,
+    robot: &Robot,
+    index: usize,
+    offer: Offer,
+    base: &Base,
+    home: &DistanceField,
+    travel: &HashMap<(usize, usize), usize>,
+) -> Option<usize> {
+    let cell = match offer {
+        Offer::Delivery(owner) => return (owner == index).then_some(0),
+        Offer::Frontier(cell) | Offer::Deposit(_, cell) => cell,
+    };
+    let (to_cell, to_base) = (*travel.get(&cell)?, home.distance(cell)?);
+    if robot.battery < to_cell + to_base + BATTERY_RESERVE {
+        return None;
+    }
+    match offer {
+        Offer::Deposit(kind, cell) => {
+            if !can_perform(map, robot, Task::Collect(kind))
+                || robot.load.keys().any(|&carried| carried != kind)
+                || base.claims.owner(cell).is_some_and(|owner| owner != index)
+            {
+                return None;
+            }
+            let trip = to_base / robot.modules.cargo_capacity().max(1);
+            Some(to_cell + trip + base.stock(kind))
+        }
+        _ => Some(to_cell),
+    }
+}
+
+// Fonction pour mener l'enchère d'un tour : à chaque passe, l'enchère la plus basse
+// l'emporte, puis son robot et son lot sont retirés, jusqu'à épuisement des lots ou des
+// candidats. Les robots sans lot ne sont pas dans le résultat. Le champ de distances de la
+// base est recalculé pour l'occasion s'il ne porte pas sur cette carte.
+pub fn run_auction(map: &Map, robots: &[Robot], base: &Base) -> Vec<Bid> {
+    let rebuilt;
+    let home = if base.home.fits(map) {
+        &base.home
+    } else {
+        rebuilt = DistanceField::new(map);
+        &rebuilt
+    };
+    let offers = offers(map, robots, base);
+    let mut targets: Vec<(usize, usize)> = offers
+        .iter()
+        .filter_map(|&offer| match offer {
+            Offer::Frontier(cell) | Offer::Deposit(_, cell) => Some(cell),
+            Offer::Delivery(_) => None,
+        })
+        .collect();
+    targets.sort_unstable();
+    targets.dedup();
+    let travel: Vec<HashMap<(usize, usize), usize>> = robots
+        .iter()
+        .map(|robot| travel_costs(map, (robot.x, robot.y), &targets, robot.battery))
+        .collect();
+
+    let mut bids: Vec<Bid> = Vec::new();
+    for offer in offers {
+        for (index, robot) in robots.iter().enumerate() {
+            if let Some(cost) = bid(map, robot, index, offer, base, home, &travel[index]) {
+                bids.push(Bid {
+                    robot: index,
+                    offer,
+                    cost,
+                });
+            }
+        }
+    }
+    bids.sort_by_key(|bid| (bid.cost, bid.robot));
+
+    let mut winners: Vec<Bid> = Vec::new();
+    for bid in bids {
+        if winners
+            .iter()
+            .all(|winner| winner.robot != bid.robot && winner.offer != bid.offer)
+        {
+            winners.push(bid);
+        }
+    }
+    winners
+}
+
+// Politique par enchères : les robots enchérissent sur les lots selon leur distance, leur
+// batterie et leur soute, et la base attribue chaque lot au moins offrant, qui le vise
+// ensuite : la frontière à explorer, le gisement qui lui est réservé ou la base où livrer.
+// Un robot sans lot garde sa tâche.
+#[derive(Debug)]
+pub struct AuctionPolicy;
+
+impl Policy for AuctionPolicy {
+    fn name(&self) -> &str {
+        "auction"
+    }
+
+    fn assign(&self, map: &Map, robots: &[Robot], base: &Base) -> Vec<Task> {
+        self.assign_targets(map, robots, base)
+            .into_iter()
+            .map(|(task, _)| task)
+            .collect()
+    }
+
+    fn assign_targets(
+        &self,
+        map: &Map,
+        robots: &[Robot],
+        base: &Base,
+    ) -> Vec<(Task, Option<(usize, usize)>)> {
+        let mut assignments: Vec<(Task, Option<(usize, usize)>)> =
+            robots.iter().map(|robot| (robot.task, None)).collect();
+        for winner in run_auction(map, robots, base) {
+            let robot = &robots[winner.robot];
+            assignments[winner.robot] = (winner.offer.task(robot), Some(winner.offer.target(map)));
+        }
+        assignments
+    }
+}
@@ -0,0 +1,227 @@
+// src/base.rs
+use crate::claims::Claims;
+use crate::distance_field::DistanceField;
+use crate::frontier::FrontierIndex;
+use crate::hpa::Hierarchy;
+use crate::modules::{upgrade_cost, Cost, ModuleKind, Modules, MAX_LEVEL};
+use crate::resources::{Extraction, ResourceKind};
+use crate::scheduler::Scheduler;
+use crate::tour::TripStats;
+use crate::traffic::Traffic;
+use crate::{Map, Robot, Task};
+use std::collections::BTreeMap;
+
+// Nombre maximal de robots dans la flotte
+pub const MAX_FLEET: usize = 8;
+
+// Stock de la base, alimenté par les robots qui déchargent leur cargaison. Les modules se
+// paient en énergie et en minerais ; les autres ressources (les points de recherche des
+// sites scientifiques par exemple) sont seulement comptées. Le planificateur de la base
+// répartit périodiquement les tâches de la flotte et fait respecter la règle de circulation ;
+// elle tient aussi le registre des gisements revendiqués par les collecteurs, mesure
+// l'efficacité de leurs voyages et entretient le champ de distances qui guide les retours,
+// l'index des frontières de l'exploration ainsi que, sur les grandes cartes, le planificateur
+// hiérarchique des trajets.
+#[derive(Debug, Default)]
+pub struct Base {
+    pub stock: BTreeMap<ResourceKind, usize>, // quantités stockées, par type de ressource
+    pub scheduler: Scheduler,                 // répartition des tâches entre les robots
+    pub traffic: Traffic,                     // règle de circulation entre les robots
+    pub claims: Claims,                       // gisements revendiqués par les collecteurs
+    pub trips: TripStats,                     // voyages de collecte terminés
+    pub home: DistanceField,                  // distances jusqu'à la base
+    pub frontier: FrontierIndex,              // frontières de l'exploration
+    pub hierarchy: Option<Hierarchy>,         // planificateur des trajets sur les grandes cartes
+}
+
+impl Base {
+    // Fonction pour créer une base au stock vide
+    pub fn new() -> Self {
+        Base::default()
+    }
+
+    // Fonction pour obtenir la quantité stockée d'un type de ressource
+    pub fn stock(&self, kind: ResourceKind) -> usize {
+        self.stock.get(&kind).copied().unwrap_or(0)
+    }
+
+    // Fonction pour construire le champ de distances et l'index des frontières d'une carte,
+    // au premier tour ou quand la base a bougé. Les cases inexplorées hors d'atteinte du champ
+    // sont marquées isolées avant de construire l'index.
+    pub fn prepare(&mut self, map: &mut Map) {
+        if self.home.fits(map) {
+            return;
+        }
+        self.home.rebuild(map);
+        for y in 0..map.height {
+            for x in 0..map.width {
+                let unreachable = self.home.distance((x, y)).is_none() && !map.is_explored((x, y));
+                map.set_unreachable((x, y), unreachable);
+            }
+        }
+        self.frontier.rebuild(map);
+    }
+
+    // Fonction pour signaler des cases modifiées hors de la simulation (éditeur, carte
+    // retouchée entre deux tours) ou découvertes au contact : les structures de la base sont
+    // réparées à partir de ces seules cases
+    pub fn edited(&mut self, map: &mut Map, cells: &[(usize, usize)]) {
+        let changed = self.home.update(map, cells.iter().copied());
+        self.reach(map, &changed);
+        self.frontier.update(map, cells.iter().copied());
+        if let Some(hierarchy) = self.hierarchy.as_mut() {
+            hierarchy.invalidate(map, cells.iter().copied());
+            hierarchy.refresh(map);
+        }
+        if let Some(fleet) = self.traffic.fleet.as_mut() {
+            fleet.heuristics.update(cells);
+        }
+    }
+
+    // Fonction pour signaler les cases révélées autour d'une position
+    pub fn revealed(&mut self, map: &mut Map, position: (usize, usize), range: usize) {
+        let changed = self.home.update_around(map, position, range);
+        self.reach(map, &changed);
+        self.frontier.update_around(map, position, range);
+        if let Some(hierarchy) = self.hierarchy.as_mut() {
+            hierarchy.invalidate_around(map, position, range);
+            hierarchy.refresh(map);
+        }
+        if let Some(fleet) = self.traffic.fleet.as_mut() {
+            fleet.heuristics.update_around(map, position, range);
+        }
+    }
+
+    // Les cases inexplorées qui ont perdu l'accès à la base ne sont plus à explorer ; celles
+    // qui l'ont retrouvé le redeviennent
+    fn reach(&mut self, map: &mut Map, changed: &[(usize, usize)]) {
+        for &cell in changed {
+            let unreachable = self.home.distance(cell).is_none() && !map.is_explored(cell);
+            map.set_unreachable(cell, unreachable);
+        }
+        self.frontier.update(map, changed.iter().copied());
+    }
+
+    // Fonction pour ajouter des ressources au stock
+    pub fn store(&mut self, kind: ResourceKind, amount: usize) {
+        *self.stock.entry(kind).or_insert(0) += amount;
+    }
+
+    // Fonction pour savoir si la base peut payer un coût ; une ressource absente du registre
+    // de la carte ne peut pas être payée
+    pub fn can_afford(&self, map: &Map, cost: &Cost) -> bool {
+        cost.amounts
+            .iter()
+            .all(|(key, &amount)| match map.resources.by_key(key) {
+                Some(kind) => self.stock(kind) >= amount,
+                None => amount == 0,
+            })
+    }
+
+    fn pay(&mut self, map: &Map, cost: &Cost) {
+        for (key, &amount) in &cost.amounts {
+            if let Some(kind) = map.resources.by_key(key) {
+                *self.stock.entry(kind).or_insert(0) -= amount;
+            }
+        }
+    }
+
+    // Fonction pour décharger la cargaison d'un robot dans le stock ; une cargaison non vide
+    // termine un voyage de collecte
+    pub fn unload(&mut self, robot: &mut Robot) {
+        let load = std::mem::take(&mut robot.load);
+        if !load.is_empty() {
+            self.trips.trips += 1;
+        }
+        for (kind, amount) in load {
+            self.trips.units += amount;
+            self.store(kind, amount);
+        }
+    }
+
+    // Fonction pour monter le niveau suivant d'un module sur un robot, si la base peut le payer
+    pub fn fit(&mut self, map: &Map, robot: &mut Robot, kind: ModuleKind) -> bool {
+        let level = robot.modules.level(kind);
+        let cost = upgrade_cost(level + 1);
+        if level >= MAX_LEVEL || !self.can_afford(map, &cost) {
+            return false;
+        }
+        self.pay(map, &cost);
+        robot.modules.upgrade(kind);
+        if kind == ModuleKind::Battery {
+            robot.battery = robot.modules.battery_capacity();
+        }
+        true
+    }
+
+    // Fonction pour monter sur un robot présent à la base le premier module utile à sa tâche
+    pub fn fit_best_upgrade(&mut self, map: &Map, robot: &mut Robot) -> Option<ModuleKind> {
+        let priorities: &[ModuleKind] = match robot.task {
+            Task::Explore => &[ModuleKind::Sensor, ModuleKind::Wheels, ModuleKind::Battery],
+            Task::Collect(kind) => match map.resources.get(kind).extraction {
+                Extraction::Drill => &[
+                    ModuleKind::Drill,
+                    ModuleKind::Cargo,
+                    ModuleKind::Battery,
+                    ModuleKind::Wheels,
+                ],
+                Extraction::Analysis => &[
+                    ModuleKind::Analysis,
+                    ModuleKind::Battery,
+                    ModuleKind::Wheels,
+                ],
+            },
+        };
+        priorities
+            .iter()
+            .copied()
+            .find(|&kind| self.fit(map, robot, kind))
+    }
+
+    // Fonction pour assembler un nouveau robot à la base, si la base peut le payer
+    pub fn assemble(&mut self, map: &Map, task: Task, modules: Modules) -> Option<Robot> {
+        let cost = modules.cost();
+        if !self.can_afford(map, &cost) {
+            return None;
+        }
+        self.pay(map, &cost);
+        Some(Robot::with_modules(map.base.0, map.base.1, task, modules))
+    }
+
+    // Fonction pour choisir le prochain robot à assembler selon les besoins : des éclaireurs
+    // tant qu'une grande partie de la carte est inconnue, puis un collecteur pour la ressource
+    // dont les gisements connus manquent le plus de collecteurs
+    pub fn plan_robot(&self, map: &Map, robots: &[Robot]) -> Option<(Task, Modules)> {
+        if robots.len() >= MAX_FLEET {
+            return None;
+        }
+
+        let cells = map.width * map.height;
+        let unexplored = self.frontier.count(map);
+        let explorers = robots.iter().filter(|r| r.task == Task::Explore).count();
+        if unexplored * 4 > cells && explorers < 2 {
+            return Some((Task::Explore, Modules::scout()));
+        }
+
+        // Un collecteur pour deux gisements connus ; à besoin égal, le premier type du registre
+        let need = |kind: ResourceKind| {
+            let known = map
+                .deposits(kind)
+                .iter()
+                .filter(|&&(x, y)| map.is_explored((x, y)))
+                .count();
+            let collectors = robots
+                .iter()
+                .filter(|r| r.task == Task::Collect(kind))
+                .count();
+            known.saturating_sub(2 * collectors)
+        };
+        let kind = map
+            .resources
+            .kinds()
+            .filter(|&kind| need(kind) > 0)
+            .max_by_key(|&kind| (need(kind), std::cmp::Reverse(kind)))?;
+        let modules = Modules::collector(map.resources.get(kind).extraction);
+        Some((Task::Collect(kind), modules))
+    }
+}
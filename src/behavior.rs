@@ -0,0 +1,435 @@
+// src/behavior.rs
+use crate::action::Action;
+use crate::clock::Clock;
+use crate::distance_field::DistanceField;
+use crate::frontier::FrontierIndex;
+use crate::hpa::Hierarchy;
+use crate::pathfinding::{find_safe_path, path_cost_with, Route};
+use crate::resources::ResourceKind;
+use crate::tour::plan_tour_with;
+use crate::{lend_memory, restore_memory, Map, Robot, RobotState, Task};
+
+// Ce qu'un robot sait de lui-même au moment de décider
+#[derive(Debug, Clone, PartialEq)]
+pub struct RobotView {
+    pub position: (usize, usize),  // position du robot
+    pub task: Task,                // tâche confiée par la base
+    pub state: RobotState,         // état actuel
+    pub battery: usize,            // charge restante
+    pub battery_capacity: usize,   // charge maximale
+    pub cargo: usize,              // unités transportées
+    pub cargo_capacity: usize,     // unités transportables
+    pub sensor_range: usize,       // portée du capteur pendant ce tour
+    pub work_ticks: Option<usize>, // tours de travail par unité collectée, None sans outil
+    pub drill_progress: usize,     // tours de travail déjà passés sur l'unité en cours
+    pub at_base: bool,             // le robot est sur la base
+    pub needs_recharge: bool,      // la batterie suffit tout juste pour rentrer
+}
+
+impl RobotView {
+    // Fonction pour observer un robot, avec la portée de capteur du moment
+    pub fn of(robot: &Robot, map: &Map, sensor_range: usize) -> Self {
+        RobotView::observe(robot, map, sensor_range, || robot.needs_recharge(map))
+    }
+
+    // Fonction pour observer un robot en lisant sa distance à la base dans le champ de
+    // distances, sans recherche de chemin
+    pub fn guided(robot: &Robot, map: &Map, sensor_range: usize, home: &DistanceField) -> Self {
+        RobotView::observe(robot, map, sensor_range, || home.needs_recharge(robot))
+    }
+
+    fn observe(
+        robot: &Robot,
+        map: &Map,
+        sensor_range: usize,
+        needs_recharge: impl FnOnce() -> bool,
+    ) -> Self {
+        let at_base = (robot.x, robot.y) == map.base;
+        RobotView {
+            position: (robot.x, robot.y),
+            task: robot.task,
+            state: robot.state,
+            battery: robot.battery,
+            battery_capacity: robot.modules.battery_capacity(),
+            cargo: robot.cargo(),
+            cargo_capacity: robot.modules.cargo_capacity(),
+            sensor_range,
+            work_ticks: robot
+                .work_ticks(map)
+                .filter(|_| robot.modules.cargo_capacity() > 0),
+            drill_progress: robot.drill_progress,
+            at_base,
+            needs_recharge: !at_base && needs_recharge(),
+        }
+    }
+
+    // Fonction pour savoir si le robot a l'outil et la soute nécessaires à sa tâche
+    pub fn can_collect(&self) -> bool {
+        self.work_ticks.is_some()
+    }
+}
+
+// Ce que la flotte sait du monde : la carte explorée, l'heure, les gisements revendiqués
+// par les autres collecteurs et, si la base les entretient, le champ de distances jusqu'à elle,
+// l'index des frontières de l'exploration et le planificateur hiérarchique des grandes cartes
+pub struct Knowledge<'a> {
+    pub map: &'a Map,     // carte partagée (seules les cases explorées sont fiables)
+    pub clock: &'a Clock, // horloge de la simulation
+    pub claimed: Vec<(usize, usize)>, // gisements revendiqués par d'autres robots, triés
+    pub home: Option<&'a DistanceField>, // distances jusqu'à la base
+    pub frontier: Option<&'a FrontierIndex>, // frontières de l'exploration
+    pub hierarchy: Option<&'a Hierarchy>, // planificateur des grandes cartes
+}
+
+impl<'a> Knowledge<'a> {
+    // Fonction pour rassembler les connaissances de la flotte
+    pub fn new(map: &'a Map, clock: &'a Clock) -> Self {
+        Knowledge {
+            map,
+            clock,
+            claimed: Vec::new(),
+            home: None,
+            frontier: None,
+            hierarchy: None,
+        }
+    }
+
+    // Fonction pour écarter des gisements revendiqués par d'autres robots
+    pub fn excluding(mut self, mut claimed: Vec<(usize, usize)>) -> Self {
+        claimed.sort_unstable();
+        self.claimed = claimed;
+        self
+    }
+
+    // Fonction pour guider les retours à la base par un champ de distances
+    pub fn with_home(mut self, home: &'a DistanceField) -> Self {
+        self.home = Some(home);
+        self
+    }
+
+    // Fonction pour trouver les cases inexplorées par l'index des frontières, s'il porte
+    // sur cette carte
+    pub fn with_frontier(mut self, frontier: &'a FrontierIndex) -> Self {
+        self.frontier = Some(frontier).filter(|frontier| frontier.fits(self.map));
+        self
+    }
+
+    // Fonction pour planifier les trajets par un planificateur hiérarchique
+    pub fn with_hierarchy(mut self, hierarchy: &'a Hierarchy) -> Self {
+        self.hierarchy = Some(hierarchy);
+        self
+    }
+
+    // Fonction pour obtenir le coût du chemin prudent d'une case jusqu'à la base, None si
+    // elle est hors d'atteinte
+    pub fn home_cost(&self, from: (usize, usize)) -> Option<usize> {
+        match self.home {
+            Some(home) => home.distance(from),
+            None => {
+                let path = find_safe_path(self.map, from, self.map.base)?;
+                Some(path_cost_with(self.map, &path, Map::safe_cost))
+            }
+        }
+    }
+
+    // Fonction pour savoir si toute la carte est explorée
+    pub fn fully_explored(&self) -> bool {
+        match self.frontier {
+            Some(frontier) => frontier.is_complete(),
+            None => self
+                .map
+                .positions()
+                .all(|cell| !self.map.needs_exploration(cell)),
+        }
+    }
+
+    // Fonction pour savoir si un gisement d'un type de ressource est atteignable et libre
+    pub fn is_free_deposit(&self, kind: ResourceKind, pos: (usize, usize)) -> bool {
+        self.map.deposit_at(pos) == Some(kind)
+            && self.map.known_cost(pos).is_some()
+            && self.claimed.binary_search(&pos).is_err()
+    }
+
+    // Fonction pour trouver le gisement atteignable et libre le plus proche d'un type de
+    // ressource
+    pub fn nearest_deposit(
+        &self,
+        kind: ResourceKind,
+        from: (usize, usize),
+    ) -> Option<(usize, usize)> {
+        self.map
+            .nearest_deposit(kind, from, |pos| self.is_free_deposit(kind, pos))
+    }
+
+    // Fonction pour trouver le gisement atteignable et libre d'un type de ressource le moins
+    // coûteux à atteindre par le chemin connu, hors de ceux à écarter et dans la limite d'un
+    // coût, avec le coût de ce chemin
+    pub fn nearest_deposit_by_path(
+        &self,
+        kind: ResourceKind,
+        from: (usize, usize),
+        skip: &[(usize, usize)],
+        limit: usize,
+    ) -> Option<((usize, usize), usize)> {
+        self.map
+            .nearest_deposit_by_path(kind, from, Map::known_cost, limit, |pos| {
+                !skip.contains(&pos) && self.is_free_deposit(kind, pos)
+            })
+    }
+
+    // Fonction pour trouver la case inexplorée la plus proche
+    pub fn nearest_unexplored(&self, from: (usize, usize)) -> Option<(usize, usize)> {
+        if let Some(frontier) = self.frontier {
+            return frontier.nearest(from);
+        }
+        (0..self.map.height)
+            .flat_map(|y| (0..self.map.width).map(move |x| (x, y)))
+            .filter(|&(x, y)| self.map.needs_exploration((x, y)))
+            .min_by_key(|&(x, y)| x.abs_diff(from.0) + y.abs_diff(from.1))
+    }
+}
+
+// Mémoire d'un robot d'un tour à l'autre : son état, l'itinéraire et la tournée en cours
+#[derive(Debug, Clone, PartialEq)]
+pub struct Memory {
+    pub state: RobotState,            // état du robot
+    pub route: Route,                 // itinéraire suivi
+    pub tour: Vec<(usize, usize)>,    // gisements restant à visiter avant de rentrer
+    pub goal: Option<(usize, usize)>, // case à explorer attribuée par la base
+}
+
+impl Default for Memory {
+    fn default() -> Self {
+        Memory {
+            state: RobotState::Exploring,
+            route: Route::default(),
+            tour: Vec::new(),
+            goal: None,
+        }
+    }
+}
+
+// Stratégie de décision d'un robot. Les robots sans stratégie personnalisée suivent la
+// stratégie intégrée de leur tâche : Explorer ou Collector.
+pub trait Behavior {
+    // Fonction pour nommer la stratégie
+    fn name(&self) -> &str;
+
+    // Fonction pour choisir l'action du tour
+    fn decide(&mut self, robot: &RobotView, knowledge: &Knowledge) -> Action;
+}
+
+// Stratégie des éclaireurs : explorer tant que la carte n'est pas connue, puis attendre à la base
+#[derive(Debug, Default)]
+pub struct Explorer {
+    pub memory: Memory,
+}
+
+impl Behavior for Explorer {
+    fn name(&self) -> &str {
+        "explorer"
+    }
+
+    fn decide(&mut self, robot: &RobotView, knowledge: &Knowledge) -> Action {
+        explorer(&mut self.memory, robot, knowledge)
+    }
+}
+
+// Stratégie des collecteurs : explorer avec les éclaireurs, puis collecter la ressource de
+// leur tâche une fois rentrés à la base
+#[derive(Debug, Default)]
+pub struct Collector {
+    pub memory: Memory,
+}
+
+impl Behavior for Collector {
+    fn name(&self) -> &str {
+        "collector"
+    }
+
+    fn decide(&mut self, robot: &RobotView, knowledge: &Knowledge) -> Action {
+        collector(&mut self.memory, robot, knowledge)
+    }
+}
+
+fn explorer(memory: &mut Memory, robot: &RobotView, knowledge: &Knowledge) -> Action {
+    if robot.needs_recharge || memory.state == RobotState::Returning {
+        if let Some(action) = dock(memory, robot, knowledge) {
+            return action;
+        }
+    }
+    if knowledge.fully_explored() {
+        return dock(memory, robot, knowledge).unwrap_or(Action::Wait);
+    }
+    explore(memory, robot, knowledge)
+}
+
+fn collector(memory: &mut Memory, robot: &RobotView, knowledge: &Knowledge) -> Action {
+    if robot.needs_recharge || memory.state == RobotState::Returning {
+        if let Some(action) = dock(memory, robot, knowledge) {
+            return action;
+        }
+        // Amarré à la base : reprendre l'exploration si elle n'est pas finie
+        memory.state = if knowledge.fully_explored() {
+            RobotState::Collecting
+        } else {
+            RobotState::Exploring
+        };
+    }
+    if memory.state == RobotState::Exploring {
+        if !knowledge.fully_explored() {
+            return explore(memory, robot, knowledge);
+        }
+        // Exploration terminée : rentrer à la base avant de collecter
+        if let Some(action) = dock(memory, robot, knowledge) {
+            return action;
+        }
+    }
+    collect(memory, robot, knowledge)
+}
+
+// Fonction pour avancer vers la case à explorer attribuée par la base, tant qu'elle reste à
+// explorer et atteignable, sinon vers la case inexplorée la plus proche
+pub fn explore(memory: &mut Memory, robot: &RobotView, knowledge: &Knowledge) -> Action {
+    memory.state = RobotState::Exploring;
+    if let Some(goal) = memory.goal {
+        if knowledge.map.needs_exploration(goal) {
+            if let Some(action) = travel(memory, robot, knowledge, goal) {
+                return action;
+            }
+        }
+        memory.goal = None;
+    }
+    knowledge
+        .nearest_unexplored(robot.position)
+        .and_then(|target| travel(memory, robot, knowledge, target))
+        .unwrap_or(Action::Wait)
+}
+
+// Fonction pour choisir l'action de collecte : suivre une tournée de gisements du type de la
+// tâche qui remplit la soute dans la limite de la batterie, travailler chaque gisement, puis
+// rentrer quand la soute est pleine ou qu'il n'y a plus rien à collecter à portée. Le robot
+// n'est en collecte que lorsqu'il se dirige vers un gisement ou le travaille.
+pub fn collect(memory: &mut Memory, robot: &RobotView, knowledge: &Knowledge) -> Action {
+    // Sans outil ni soute, le robot attend à la base
+    let (Task::Collect(kind), Some(work_ticks)) = (robot.task, robot.work_ticks) else {
+        memory.tour.clear();
+        return dock(memory, robot, knowledge).unwrap_or(Action::Wait);
+    };
+    if robot.cargo >= robot.cargo_capacity {
+        memory.tour.clear();
+        return dock(memory, robot, knowledge).unwrap_or(Action::Wait);
+    }
+    // Une étape revendiquée entre-temps par un autre robot fait replanifier toute la tournée ;
+    // les étapes épuisées sont abandonnées. Une tournée vide est replanifiée depuis la position
+    // du robot.
+    if memory
+        .tour
+        .iter()
+        .any(|pos| knowledge.claimed.binary_search(pos).is_ok())
+    {
+        memory.tour.clear();
+    }
+    memory
+        .tour
+        .retain(|&pos| knowledge.is_free_deposit(kind, pos));
+    if memory.tour.is_empty() {
+        // Chaque étape est le gisement libre le moins coûteux à atteindre depuis la
+        // précédente, cherché dans l'index des gisements sans dépasser la batterie
+        memory.tour = plan_tour_with(
+            knowledge.map,
+            robot.position,
+            |from, tour| knowledge.nearest_deposit_by_path(kind, from, tour, robot.battery),
+            robot.cargo_capacity - robot.cargo,
+            robot.battery,
+            |cell| knowledge.home_cost(cell),
+        );
+    }
+    match memory.tour.first().copied() {
+        Some(target) if target == robot.position => {
+            memory.state = RobotState::Collecting;
+            // L'étape reste dans la tournée : le gisement épuisé en sera retiré au tour
+            // suivant, et un forage refusé sera repris
+            if robot.drill_progress + 1 >= work_ticks && robot.cargo + 1 >= robot.cargo_capacity {
+                // La soute sera pleine après ce tour de travail : le robot rentrera ensuite
+                memory.state = RobotState::Returning;
+            }
+            Action::Mine
+        }
+        Some(target) => match travel(memory, robot, knowledge, target) {
+            Some(action) => {
+                memory.state = RobotState::Collecting;
+                action
+            }
+            None => {
+                memory.tour.clear();
+                dock(memory, robot, knowledge).unwrap_or(Action::Wait)
+            }
+        },
+        None => dock(memory, robot, knowledge).unwrap_or(Action::Wait),
+    }
+}
+
+// Fonction pour rentrer à la base par un chemin prudent, puis y décharger la cargaison et
+// recharger la batterie ; None une fois le robot amarré et prêt à repartir
+pub fn dock(memory: &mut Memory, robot: &RobotView, knowledge: &Knowledge) -> Option<Action> {
+    memory.state = RobotState::Returning;
+    if !robot.at_base {
+        let (map, base) = (knowledge.map, knowledge.map.base);
+        let direction = match knowledge.home {
+            Some(home) => home.next_step(&mut memory.route, map, robot.position),
+            None => memory
+                .route
+                .next_step(map, robot.position, base, Map::safe_cost),
+        };
+        return Some(direction.map_or(Action::Wait, Action::Move));
+    }
+    if robot.cargo > 0 {
+        Some(Action::Unload)
+    } else if robot.battery < robot.battery_capacity {
+        Some(Action::Recharge)
+    } else {
+        None
+    }
+}
+
+// Fonction pour faire un pas vers une case par le chemin connu le moins coûteux ; None si
+// aucun chemin connu n'y mène
+pub fn travel(
+    memory: &mut Memory,
+    robot: &RobotView,
+    knowledge: &Knowledge,
+    target: (usize, usize),
+) -> Option<Action> {
+    if robot.position == target {
+        return Some(Action::Wait);
+    }
+    let (map, from) = (knowledge.map, robot.position);
+    let direction = match knowledge.hierarchy {
+        Some(hierarchy) => memory.route.follow(map, from, target, Map::known_cost, || {
+            hierarchy.find_path(map, from, target)
+        }),
+        None => memory.route.next_step(map, from, target, Map::known_cost),
+    };
+    direction.map(Action::Move)
+}
+
+// Fonction pour choisir l'action d'un robot : sa stratégie personnalisée s'il en a une, sinon
+// celle de sa tâche, qui s'appuie sur l'état et l'itinéraire mémorisés par le robot
+pub fn decide(robot: &mut Robot, view: &RobotView, knowledge: &Knowledge) -> Action {
+    if let Some(behavior) = robot.behavior.as_mut() {
+        return behavior.decide(view, knowledge);
+    }
+    let mut memory = lend_memory(robot);
+    let action = decide_by_task(&mut memory, view, knowledge);
+    restore_memory(robot, memory);
+    action
+}
+
+// Fonction pour choisir l'action avec la stratégie intégrée de la tâche du robot
+pub fn decide_by_task(memory: &mut Memory, view: &RobotView, knowledge: &Knowledge) -> Action {
+    match view.task {
+        Task::Explore => explorer(memory, view, knowledge),
+        Task::Collect(_) => collector(memory, view, knowledge),
+    }
+}
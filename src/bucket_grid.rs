@@ -0,0 +1,127 @@
+// src/bucket_grid.rs
+use std::collections::HashMap;
+
+// Côté des paquets de cases
+pub const BUCKET_SIZE: usize = 16;
+
+// Fonction pour obtenir la distance de Manhattan entre deux cases
+pub fn manhattan(a: (usize, usize), b: (usize, usize)) -> usize {
+    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+}
+
+// Fonction pour obtenir la distance entre deux cases en déplacements à huit directions
+pub fn chebyshev(a: (usize, usize), b: (usize, usize)) -> usize {
+    a.0.abs_diff(b.0).max(a.1.abs_diff(b.1))
+}
+
+// Ensemble de cases rangées par paquets carrés de la carte. La place de chaque case dans son
+// paquet est retenue : ajouter, retirer ou tester une case prend un temps constant. Chercher
+// la case la plus proche ne parcourt que les paquets autour de la position, par anneaux,
+// jusqu'à ce qu'aucun paquet plus loin ne puisse faire mieux ; à distance égale, la première
+// case dans l'ordre des lignes l'emporte.
+#[derive(Debug, Default, Clone)]
+pub struct BucketGrid {
+    columns: usize,                        // nombre de paquets par ligne
+    rows: usize,                           // nombre de paquets par colonne
+    buckets: Vec<Vec<(usize, usize)>>,     // cases de chaque paquet
+    slots: HashMap<(usize, usize), usize>, // place de chaque case dans son paquet
+}
+
+impl BucketGrid {
+    // Fonction pour créer une grille vide pour une carte de ces dimensions
+    pub fn new(width: usize, height: usize) -> Self {
+        let columns = width.div_ceil(BUCKET_SIZE);
+        let rows = height.div_ceil(BUCKET_SIZE);
+        BucketGrid {
+            columns,
+            rows,
+            buckets: vec![Vec::new(); columns * rows],
+            slots: HashMap::new(),
+        }
+    }
+
+    fn bucket(&self, (x, y): (usize, usize)) -> usize {
+        (y / BUCKET_SIZE) * self.columns + x / BUCKET_SIZE
+    }
+
+    // Fonction pour obtenir le nombre de cases rangées
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    // Fonction pour savoir si la grille est vide
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    // Fonction pour savoir si une case est rangée
+    pub fn contains(&self, cell: (usize, usize)) -> bool {
+        self.slots.contains_key(&cell)
+    }
+
+    // Fonction pour ranger une case
+    pub fn insert(&mut self, cell: (usize, usize)) {
+        if !self.contains(cell) {
+            let bucket = self.bucket(cell);
+            self.slots.insert(cell, self.buckets[bucket].len());
+            self.buckets[bucket].push(cell);
+        }
+    }
+
+    // Fonction pour retirer une case : la dernière de son paquet prend sa place
+    pub fn remove(&mut self, cell: (usize, usize)) {
+        if let Some(slot) = self.slots.remove(&cell) {
+            let bucket = self.bucket(cell);
+            self.buckets[bucket].swap_remove(slot);
+            if let Some(&moved) = self.buckets[bucket].get(slot) {
+                self.slots.insert(moved, slot);
+            }
+        }
+    }
+
+    // Fonction pour parcourir toutes les cases rangées, paquet par paquet
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.buckets.iter().flatten().copied()
+    }
+
+    // Fonction pour trouver la case acceptée la plus proche selon une distance qui n'est
+    // jamais inférieure à celle des déplacements à huit directions (Manhattan ou celle-ci)
+    pub fn nearest(
+        &self,
+        from: (usize, usize),
+        distance: fn((usize, usize), (usize, usize)) -> usize,
+        accept: impl Fn((usize, usize)) -> bool,
+    ) -> Option<(usize, usize)> {
+        if self.is_empty() {
+            return None;
+        }
+        let (bx, by) = (from.0 / BUCKET_SIZE, from.1 / BUCKET_SIZE);
+        let mut best: Option<(usize, usize, usize)> = None;
+        for ring in 0..self.columns.max(self.rows) {
+            // Une case d'un paquet de cet anneau est à au moins cette distance
+            let bound = (ring * BUCKET_SIZE).saturating_sub(BUCKET_SIZE - 1);
+            if best.is_some_and(|(distance, _, _)| distance < bound) {
+                break;
+            }
+            let (x0, x1) = (bx.saturating_sub(ring), (bx + ring).min(self.columns - 1));
+            let (y0, y1) = (by.saturating_sub(ring), (by + ring).min(self.rows - 1));
+            for cy in y0..=y1 {
+                // Sur les lignes intérieures de l'anneau, seuls ses deux bords comptent
+                let inner = cy.abs_diff(by) != ring;
+                let step = if inner { (x1 - x0).max(1) } else { 1 };
+                for cx in (x0..=x1).step_by(step) {
+                    if inner && cx.abs_diff(bx) != ring {
+                        continue;
+                    }
+                    for &(x, y) in &self.buckets[cy * self.columns + cx] {
+                        let candidate = (distance(from, (x, y)), y, x);
+                        if best.is_none_or(|best| candidate < best) && accept((x, y)) {
+                            best = Some(candidate);
+                        }
+                    }
+                }
+            }
+        }
+        best.map(|(_, y, x)| (x, y))
+    }
+}
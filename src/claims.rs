@@ -0,0 +1,111 @@
+// src/claims.rs
+use crate::{Map, Robot, Task};
+use std::collections::BTreeMap;
+
+// Nombre de tours pendant lesquels une revendication tient sans être renouvelée
+pub const CLAIM_DURATION: usize = 30;
+
+// Revendication d'un gisement par un collecteur
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Claim {
+    pub robot: usize,   // indice du robot qui a revendiqué le gisement
+    pub expires: usize, // tour à partir duquel la revendication tombe
+}
+
+// Registre des gisements revendiqués, tenu par la base. Un collecteur revendique toutes les
+// étapes de sa tournée et renouvelle ses revendications tant qu'il les vise ou les travaille ;
+// les autres collecteurs les ignorent. Un robot détourné cesse de renouveler : ses
+// revendications expirent.
+#[derive(Debug, Default)]
+pub struct Claims {
+    pub claims: BTreeMap<(usize, usize), Claim>, // revendications, par gisement
+}
+
+impl Claims {
+    // Fonction pour obtenir le robot qui a revendiqué un gisement
+    pub fn owner(&self, cell: (usize, usize)) -> Option<usize> {
+        self.claims.get(&cell).map(|claim| claim.robot)
+    }
+
+    // Fonction pour obtenir les gisements revendiqués par les autres robots
+    pub fn held_by_others(&self, robot: usize) -> Vec<(usize, usize)> {
+        self.claims
+            .iter()
+            .filter(|(_, claim)| claim.robot != robot)
+            .map(|(&cell, _)| cell)
+            .collect()
+    }
+
+    // Fonction pour revendiquer un gisement, ou renouveler la revendication ; un robot ne
+    // revendique qu'un gisement à la fois. Renvoie false si un autre robot le tient déjà.
+    pub fn claim(&mut self, cell: (usize, usize), robot: usize, tick: usize) -> bool {
+        if self.owner(cell).is_some_and(|owner| owner != robot) {
+            return false;
+        }
+        self.release(robot);
+        self.claims.insert(
+            cell,
+            Claim {
+                robot,
+                expires: tick + CLAIM_DURATION,
+            },
+        );
+        true
+    }
+
+    // Fonction pour revendiquer plusieurs gisements à la fois, ou renouveler leurs
+    // revendications ; les autres revendications du robot sont libérées. Les gisements déjà
+    // tenus par un autre robot sont laissés de côté. Renvoie le nombre de gisements tenus.
+    pub fn claim_all(
+        &mut self,
+        cells: impl IntoIterator<Item = (usize, usize)>,
+        robot: usize,
+        tick: usize,
+    ) -> usize {
+        self.release(robot);
+        let claim = Claim {
+            robot,
+            expires: tick + CLAIM_DURATION,
+        };
+        let mut held = 0;
+        for cell in cells {
+            if self.owner(cell).is_none() {
+                self.claims.insert(cell, claim);
+                held += 1;
+            }
+        }
+        held
+    }
+
+    // Fonction pour libérer les revendications d'un robot
+    pub fn release(&mut self, robot: usize) {
+        self.claims.retain(|_, claim| claim.robot != robot);
+    }
+
+    // Fonction pour retirer les revendications expirées et celles des gisements épuisés
+    pub fn expire(&mut self, map: &Map, tick: usize) {
+        self.claims
+            .retain(|&cell, claim| claim.expires > tick && map.deposit_at(cell).is_some());
+    }
+
+    // Fonction pour renouveler les revendications d'un collecteur sur les gisements de sa
+    // tâche : ceux de sa tournée, celui qu'il vise et celui sur lequel il se trouve. Un robot
+    // qui n'a plus de tâche de collecte perd ses revendications.
+    pub fn renew(&mut self, map: &Map, robot: &Robot, index: usize, tick: usize) {
+        let Task::Collect(kind) = robot.task else {
+            self.release(index);
+            return;
+        };
+        let targets: Vec<(usize, usize)> = robot
+            .route
+            .target
+            .into_iter()
+            .chain(Some((robot.x, robot.y)))
+            .chain(robot.tour.iter().copied())
+            .filter(|&cell| map.deposit_at(cell) == Some(kind))
+            .collect();
+        if !targets.is_empty() {
+            self.claim_all(targets, index, tick);
+        }
+    }
+}
@@ -0,0 +1,101 @@
+// src/clock.rs
+
+// Durée par défaut d'un cycle jour/nuit, en tours ; le jour occupe la première moitié
+pub const DAY_LENGTH: usize = 240;
+
+// Durée de l'aube et du crépuscule, pendant lesquels la lumière varie progressivement
+pub const TWILIGHT: usize = 20;
+
+// Luminosité de la scène en pleine nuit (1.0 en plein jour)
+pub const NIGHT_LIGHT: f64 = 0.35;
+
+// Les panneaux solaires rendent une unité de batterie tous les SOLAR_PERIOD tours de jour
+pub const SOLAR_PERIOD: usize = 4;
+
+// Horloge globale de la simulation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clock {
+    pub tick: usize,       // nombre de tours écoulés
+    pub day_length: usize, // durée d'un cycle jour/nuit complet
+}
+
+impl Clock {
+    // Fonction pour créer une horloge au lever du jour
+    pub fn new(day_length: usize) -> Self {
+        Clock {
+            tick: 0,
+            day_length: day_length.max(2),
+        }
+    }
+
+    // Fonction pour avancer d'un tour
+    pub fn advance(&mut self) {
+        self.tick += 1;
+    }
+
+    // Fonction pour obtenir le numéro du jour, en commençant à 1
+    pub fn day(&self) -> usize {
+        self.tick / self.day_length + 1
+    }
+
+    // Fonction pour obtenir l'heure : le tour courant dans le cycle
+    pub fn time_of_day(&self) -> usize {
+        self.tick % self.day_length
+    }
+
+    // Fonction pour savoir s'il fait jour
+    pub fn is_day(&self) -> bool {
+        self.time_of_day() < self.day_length / 2
+    }
+
+    // Fonction pour obtenir la luminosité de la scène, entre NIGHT_LIGHT et 1.0
+    pub fn light(&self) -> f64 {
+        let half = self.day_length / 2;
+        let twilight = TWILIGHT.min(half / 2).max(1) as f64;
+        let time = self.time_of_day();
+        // Part du jour : montée à l'aube, descente au crépuscule
+        let daylight = if time < half {
+            let since_dawn = time as f64 + 0.5;
+            let until_dusk = (half - time) as f64 - 0.5;
+            (since_dawn.min(until_dusk) / twilight).min(1.0)
+        } else {
+            0.0
+        };
+        NIGHT_LIGHT + (1.0 - NIGHT_LIGHT) * daylight
+    }
+
+    // Fonction pour obtenir la recharge solaire d'un robot pendant ce tour
+    pub fn solar_charge(&self) -> usize {
+        if self.is_day() && self.tick.is_multiple_of(SOLAR_PERIOD) {
+            1
+        } else {
+            0
+        }
+    }
+
+    // Fonction pour obtenir la portée du capteur : elle diminue d'une case la nuit
+    pub fn sensor_range(&self, range: usize) -> usize {
+        if self.is_day() {
+            range
+        } else {
+            range.saturating_sub(1)
+        }
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Clock::new(DAY_LENGTH)
+    }
+}
+
+// Fonction pour assombrir une image selon la luminosité (1.0 la laisse intacte)
+pub fn shade(buffer: &mut [u32], light: f64) {
+    if light >= 1.0 {
+        return;
+    }
+    for pixel in buffer.iter_mut() {
+        let channel = |shift: u32| ((((*pixel >> shift) & 0xFF) as f64 * light) as u32) << shift;
+        *pixel = (*pixel & 0xFF00_0000) | channel(16) | channel(8) | channel(0);
+    }
+}
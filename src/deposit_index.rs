@@ -0,0 +1,113 @@
+// src/deposit_index.rs
+use crate::bucket_grid::{chebyshev, manhattan, BucketGrid};
+use crate::pathfinding::CostFn;
+use crate::resources::ResourceKind;
+use crate::Map;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+// Index spatial des gisements d'un type de ressource, rangés dans une grille de paquets :
+// chercher le plus proche ne parcourt que les paquets autour de la position, et ajouter ou
+// retirer un gisement prend un temps constant.
+#[derive(Debug, Default, Clone)]
+pub struct DepositIndex {
+    grid: BucketGrid, // gisements présents
+}
+
+impl DepositIndex {
+    // Fonction pour créer un index vide pour une carte de ces dimensions
+    pub fn new(width: usize, height: usize) -> Self {
+        DepositIndex {
+            grid: BucketGrid::new(width, height),
+        }
+    }
+
+    // Fonction pour obtenir le nombre de gisements présents
+    pub fn len(&self) -> usize {
+        self.grid.len()
+    }
+
+    // Fonction pour savoir si l'index est vide
+    pub fn is_empty(&self) -> bool {
+        self.grid.is_empty()
+    }
+
+    // Fonction pour savoir si une case porte un gisement de l'index
+    pub fn contains(&self, cell: (usize, usize)) -> bool {
+        self.grid.contains(cell)
+    }
+
+    // Fonction pour ajouter un gisement
+    pub fn insert(&mut self, cell: (usize, usize)) {
+        self.grid.insert(cell);
+    }
+
+    // Fonction pour retirer un gisement épuisé ou effacé
+    pub fn remove(&mut self, cell: (usize, usize)) {
+        self.grid.remove(cell);
+    }
+
+    // Fonction pour trouver le gisement accepté le plus proche selon la distance de Manhattan
+    pub fn nearest(
+        &self,
+        from: (usize, usize),
+        accept: impl Fn((usize, usize)) -> bool,
+    ) -> Option<(usize, usize)> {
+        self.nearest_with(from, manhattan, accept)
+    }
+
+    // Fonction pour trouver le gisement accepté le plus proche selon une distance qui n'est
+    // jamais inférieure à celle des déplacements à huit directions (Manhattan ou celle-ci)
+    pub fn nearest_with(
+        &self,
+        from: (usize, usize),
+        distance: fn((usize, usize), (usize, usize)) -> usize,
+        accept: impl Fn((usize, usize)) -> bool,
+    ) -> Option<(usize, usize)> {
+        self.grid.nearest(from, distance, accept)
+    }
+
+    // Fonction pour trouver le gisement accepté le moins coûteux à atteindre par un chemin
+    // d'au plus `limit`, avec le coût de ce chemin. La recherche (Dijkstra) s'arrête au
+    // premier gisement atteint ou aux chemins trop coûteux ; les gisements sont reconnus sur
+    // les cases de la carte, l'index portant sur ceux de type `kind`.
+    pub fn nearest_by_path(
+        &self,
+        map: &Map,
+        kind: ResourceKind,
+        from: (usize, usize),
+        cost_of: CostFn,
+        limit: usize,
+        accept: impl Fn((usize, usize)) -> bool,
+    ) -> Option<((usize, usize), usize)> {
+        // Sans gisement accepté à portée, inutile de parcourir la carte : chaque pas coûte au
+        // moins un
+        let closest = self.nearest_with(from, chebyshev, &accept)?;
+        if chebyshev(from, closest) > limit {
+            return None;
+        }
+        let mut costs = HashMap::new();
+        let mut open = BinaryHeap::new();
+        costs.insert(from, 0);
+        open.push(Reverse((0, from)));
+        while let Some(Reverse((cost, cell))) = open.pop() {
+            if costs.get(&cell).is_some_and(|&known| cost > known) {
+                continue;
+            }
+            if map.deposit_at(cell) == Some(kind) && accept(cell) {
+                return Some((cell, cost));
+            }
+            for next in map.neighbors(cell) {
+                let Some(step) = cost_of(map, next) else {
+                    continue;
+                };
+                if cost + step <= limit && costs.get(&next).is_none_or(|&known| cost + step < known)
+                {
+                    costs.insert(next, cost + step);
+                    open.push(Reverse((cost + step, next)));
+                }
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,352 @@
+// src/distance_field.rs
+use crate::action::Direction;
+use crate::pathfinding::{neighbors, CostFn, Route};
+use crate::{Map, Robot, BATTERY_RESERVE};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+// Valeur stockée pour une case sans coût (obstacle) ou sans distance (cible hors d'atteinte)
+const UNSET: u32 = u32::MAX;
+
+// Fonctions pour ranger une valeur facultative sur 4 octets, et pour la relire
+fn pack(value: Option<usize>) -> u32 {
+    value.map_or(UNSET, |value| value as u32)
+}
+
+fn unpack(value: u32) -> Option<usize> {
+    (value != UNSET).then_some(value as usize)
+}
+
+// Champ de distances jusqu'à une cible (carte de Dijkstra) : pour chaque case, le coût du
+// chemin le moins coûteux jusqu'à la cible. Le champ de la base suit la base de la carte et
+// compte le coût prudent, les cases inexplorées étant supposées accidentées. Il est réparé sur
+// place au fil des découvertes, à partir des seules cases modifiées ; rentrer à la base ou
+// vérifier la batterie ne demande alors plus de recherche de chemin.
+#[derive(Debug, Clone)]
+pub struct DistanceField {
+    width: usize,
+    height: usize,
+    goal: (usize, usize),
+    follows_base: bool,  // true si la cible est la base de la carte
+    cost_of: CostFn,     // coût d'une case
+    costs: Vec<u32>,     // coût de chaque case au dernier calcul, UNSET pour un obstacle
+    distances: Vec<u32>, // coût jusqu'à la cible, UNSET si elle est hors d'atteinte
+    pub rebuilds: usize, // recalculs complets
+    pub repairs: usize,  // réparations partielles après une découverte
+}
+
+impl Default for DistanceField {
+    fn default() -> Self {
+        DistanceField {
+            width: 0,
+            height: 0,
+            goal: (0, 0),
+            follows_base: true,
+            cost_of: Map::safe_cost,
+            costs: Vec::new(),
+            distances: Vec::new(),
+            rebuilds: 0,
+            repairs: 0,
+        }
+    }
+}
+
+impl DistanceField {
+    // Fonction pour calculer le champ de distances jusqu'à la base d'une carte
+    pub fn new(map: &Map) -> Self {
+        let mut field = DistanceField::default();
+        field.rebuild(map);
+        field
+    }
+
+    // Fonction pour calculer le champ de distances jusqu'à une case fixe, avec une fonction de
+    // coût donnée
+    pub fn towards(map: &Map, goal: (usize, usize), cost_of: CostFn) -> Self {
+        let mut field = DistanceField {
+            goal,
+            follows_base: false,
+            cost_of,
+            ..DistanceField::default()
+        };
+        field.rebuild(map);
+        field
+    }
+
+    fn index(&self, (x, y): (usize, usize)) -> usize {
+        y * self.width + x
+    }
+
+    fn cost(&self, index: usize) -> Option<usize> {
+        unpack(self.costs[index])
+    }
+
+    fn distance_at(&self, index: usize) -> Option<usize> {
+        unpack(self.distances[index])
+    }
+
+    // Fonction pour savoir si le champ porte sur cette carte, et sur sa base pour le champ de
+    // la base
+    pub fn fits(&self, map: &Map) -> bool {
+        (self.width, self.height) == (map.width, map.height)
+            && (!self.follows_base || self.goal == map.base)
+    }
+
+    // Fonction pour obtenir la case visée par le champ
+    pub fn goal(&self) -> (usize, usize) {
+        self.goal
+    }
+
+    // Fonction pour obtenir le coût du chemin d'une case jusqu'à la cible, None si la cible
+    // est hors d'atteinte
+    pub fn distance(&self, cell: (usize, usize)) -> Option<usize> {
+        self.distance_at(self.index(cell))
+    }
+
+    // Fonction pour recalculer tout le champ depuis la cible
+    pub fn rebuild(&mut self, map: &Map) {
+        self.width = map.width;
+        self.height = map.height;
+        if self.follows_base {
+            self.goal = map.base;
+        }
+        let cost_of = self.cost_of;
+        self.costs = (0..map.height)
+            .flat_map(|y| (0..map.width).map(move |x| (x, y)))
+            .map(|cell| pack(cost_of(map, cell)))
+            .collect();
+        self.distances = vec![UNSET; map.width * map.height];
+        self.rebuilds += 1;
+        let goal = self.index(self.goal);
+        self.distances[goal] = 0;
+        self.propagate(map, vec![self.goal]);
+    }
+
+    // Propagation de Dijkstra depuis des cases dont la distance vient de baisser : le coût
+    // d'un pas est celui de la case d'arrivée. Renvoie les cases qui n'avaient pas de distance.
+    fn propagate(&mut self, map: &Map, from: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+        let mut reached = Vec::new();
+        let mut open: BinaryHeap<_> = from
+            .into_iter()
+            .filter_map(|cell| Some(Reverse((self.distance(cell)?, cell))))
+            .collect();
+        while let Some(Reverse((distance, cell))) = open.pop() {
+            if self.distance(cell) != Some(distance) {
+                continue;
+            }
+            let Some(step) = self.cost(self.index(cell)) else {
+                continue;
+            };
+            for next in neighbors(map, cell) {
+                let index = self.index(next);
+                if self.costs[index] != UNSET
+                    && self
+                        .distance_at(index)
+                        .is_none_or(|known| distance + step < known)
+                {
+                    if self.distances[index] == UNSET {
+                        reached.push(next);
+                    }
+                    self.distances[index] = (distance + step) as u32;
+                    open.push(Reverse((distance + step, next)));
+                }
+            }
+        }
+        reached
+    }
+
+    // Fonction pour savoir si une case tient sa distance d'une voisine : une voisine dont la
+    // distance plus le coût donne exactement la sienne
+    fn is_supported(&self, map: &Map, cell: (usize, usize), distance: usize) -> bool {
+        neighbors(map, cell).any(|next| {
+            let index = self.index(next);
+            let through = self.distance_at(index).zip(self.cost(index));
+            through.is_some_and(|(known, cost)| known + cost == distance)
+        })
+    }
+
+    // Retrait des distances qui passaient par des cases devenues plus chères : en partant des
+    // voisines de ces cases, par distance croissante, une case qu'aucune voisine ne soutient
+    // plus perd sa distance et ses voisines plus éloignées sont examinées à leur tour. Les
+    // soutiens d'une case sont plus proches de la cible qu'elle : ils sont déjà tranchés quand
+    // elle est examinée. Renvoie les cases qui ont perdu leur distance.
+    fn invalidate(&mut self, map: &Map, raised: &[(usize, usize)]) -> Vec<(usize, usize)> {
+        let mut invalid = Vec::new();
+        let mut open = BinaryHeap::new();
+        for &cell in raised {
+            let index = self.index(cell);
+            let Some(distance) = self.distance_at(index) else {
+                continue;
+            };
+            if self.costs[index] == UNSET {
+                self.distances[index] = UNSET;
+                invalid.push(cell);
+            }
+            open.extend(
+                neighbors(map, cell)
+                    .filter_map(|next| Some(Reverse((self.distance(next)?, next))))
+                    .filter(|&Reverse((known, _))| known > distance),
+            );
+        }
+        while let Some(Reverse((distance, cell))) = open.pop() {
+            if cell == self.goal
+                || self.distance(cell) != Some(distance)
+                || self.is_supported(map, cell, distance)
+            {
+                continue;
+            }
+            let index = self.index(cell);
+            self.distances[index] = UNSET;
+            invalid.push(cell);
+            open.extend(
+                neighbors(map, cell)
+                    .filter_map(|next| Some(Reverse((self.distance(next)?, next))))
+                    .filter(|&Reverse((known, _))| known > distance),
+            );
+        }
+        invalid
+    }
+
+    // Fonction pour prendre en compte le nouveau coût de cases modifiées (découvertes, obstacles
+    // heurtés, éditeur). Seules ces cases et celles dont le chemin vers la cible passait par
+    // elles sont recalculées : une case devenue plus chère ou infranchissable retire les
+    // distances qu'elle soutenait, puis les cases touchées et les cases devenues moins chères
+    // reprennent la meilleure distance de leurs voisines et la propagent. Renvoie les cases
+    // qui ont perdu ou retrouvé l'accès à la cible, toutes les cases si le champ a été recalculé.
+    pub fn update(
+        &mut self,
+        map: &Map,
+        cells: impl IntoIterator<Item = (usize, usize)>,
+    ) -> Vec<(usize, usize)> {
+        if !self.fits(map) {
+            self.rebuild(map);
+            return map.positions().collect();
+        }
+        let mut lowered = Vec::new();
+        let mut raised = Vec::new();
+        for cell in cells {
+            let index = self.index(cell);
+            let (old, new) = (self.cost(index), (self.cost_of)(map, cell));
+            if old == new {
+                continue;
+            }
+            self.costs[index] = pack(new);
+            match (old, new) {
+                (None, _) => lowered.push(cell),
+                (Some(old), Some(new)) if new < old => lowered.push(cell),
+                _ => raised.push(cell),
+            }
+        }
+        if lowered.is_empty() && raised.is_empty() {
+            return Vec::new();
+        }
+        self.repairs += 1;
+        let invalid = self.invalidate(map, &raised);
+        let mut seeds = invalid.clone();
+        seeds.extend(lowered);
+        let mut reached = Vec::new();
+        for &cell in &seeds {
+            let index = self.index(cell);
+            if self.costs[index] == UNSET {
+                continue;
+            }
+            let best = neighbors(map, cell)
+                .filter_map(|next| Some(self.distance(next)? + self.cost(self.index(next))?))
+                .min();
+            if best.is_some_and(|best| self.distance_at(index).is_none_or(|known| best < known)) {
+                if self.distances[index] == UNSET {
+                    reached.push(cell);
+                }
+                self.distances[index] = pack(best);
+            }
+        }
+        reached.extend(self.propagate(map, seeds));
+
+        // Les cases invalidées avaient une distance : seules comptent celles restées sans
+        let invalid_set: HashSet<(usize, usize)> = invalid.iter().copied().collect();
+        let mut changed: Vec<(usize, usize)> = invalid
+            .into_iter()
+            .filter(|&cell| self.distance(cell).is_none())
+            .collect();
+        changed.extend(
+            reached
+                .into_iter()
+                .filter(|cell| !invalid_set.contains(cell)),
+        );
+        changed
+    }
+
+    // Fonction pour prendre en compte les cases révélées autour d'une position
+    pub fn update_around(
+        &mut self,
+        map: &Map,
+        (x, y): (usize, usize),
+        range: usize,
+    ) -> Vec<(usize, usize)> {
+        let cells = (y.saturating_sub(range)..=(y + range).min(map.height - 1)).flat_map(|y| {
+            (x.saturating_sub(range)..=(x + range).min(map.width - 1)).map(move |x| (x, y))
+        });
+        self.update(map, cells)
+    }
+
+    // Fonction pour savoir si un robot doit rentrer recharger sa batterie
+    pub fn needs_recharge(&self, robot: &Robot) -> bool {
+        self.distance((robot.x, robot.y))
+            .is_some_and(|distance| robot.battery <= distance + BATTERY_RESERVE)
+    }
+
+    // Fonction pour savoir si une voisine rapproche de la cible par un chemin le moins coûteux
+    fn is_downhill(&self, from: (usize, usize), next: (usize, usize)) -> bool {
+        let index = self.index(next);
+        match (
+            self.distance(from),
+            self.distance_at(index),
+            self.cost(index),
+        ) {
+            (Some(from), Some(distance), Some(cost)) => distance + cost == from,
+            _ => false,
+        }
+    }
+
+    // Fonction pour obtenir le chemin le moins coûteux d'une case jusqu'à la cible, en
+    // descendant le champ ; vide si la cible est hors d'atteinte
+    pub fn path_home(&self, map: &Map, from: (usize, usize)) -> Vec<(usize, usize)> {
+        let mut path = Vec::new();
+        let mut cell = from;
+        while cell != self.goal {
+            match neighbors(map, cell).find(|&next| self.is_downhill(cell, next)) {
+                Some(next) => {
+                    path.push(next);
+                    cell = next;
+                }
+                None => return Vec::new(),
+            }
+        }
+        path
+    }
+
+    // Fonction pour obtenir la direction du prochain pas vers la cible. L'itinéraire mémorisé
+    // est gardé tant que son premier pas descend le champ ; None si le robot est sur la cible
+    // ou si elle est hors d'atteinte.
+    pub fn next_step(
+        &self,
+        route: &mut Route,
+        map: &Map,
+        from: (usize, usize),
+    ) -> Option<Direction> {
+        if route.target != Some(self.goal) {
+            route.path.clear();
+        }
+        route.target = Some(self.goal);
+        if let Some(index) = route.path.iter().position(|&cell| cell == from) {
+            route.path.drain(..=index);
+        }
+        if route
+            .path
+            .first()
+            .is_none_or(|&next| !self.is_downhill(from, next))
+        {
+            route.path = self.path_home(map, from);
+        }
+        Direction::towards(from, *route.path.first()?)
+    }
+}
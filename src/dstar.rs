@@ -0,0 +1,204 @@
+// src/dstar.rs
+use crate::pathfinding::{neighbors, CostFn};
+use crate::Map;
+use std::collections::{BTreeSet, HashMap};
+
+// Coût infini : case d'où la cible est hors d'atteinte
+const INFINITY: usize = usize::MAX;
+
+// Clé de priorité d'une case dans la file de D* Lite
+type Key = (usize, usize);
+
+// Case de la carte
+type Cell = (usize, usize);
+
+// Recherche incrémentale (D* Lite) : les coûts sont calculés depuis la cible, si bien que le
+// robot peut avancer sans invalider la recherche. Quand le coût d'une case change, seules les
+// cases concernées sont réexaminées au lieu de tout recalculer. Le coût d'une case n'est relevé
+// que lorsque la recherche l'examine, puis gardé jusqu'à ce qu'il soit mis à jour ; les cases
+// du chemin sont réexaminées à chaque pas. Seules les cases atteintes par la recherche occupent
+// de la mémoire.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DStarLite {
+    width: usize,
+    height: usize,
+    pub goal: Cell,                      // cible de la recherche
+    start: Cell,                         // position du robot
+    km: usize,                           // cumul des déplacements du robot, ajouté aux clés
+    costs: HashMap<Cell, Option<usize>>, // coût relevé des cases examinées
+    g: HashMap<Cell, usize>,             // coût jusqu'à la cible, depuis les cases atteintes
+    rhs: HashMap<Cell, usize>,           // coût prévu d'après les voisines
+    queued: HashMap<Cell, Key>,          // clé de chaque case présente dans la file
+    open: BTreeSet<(Key, Cell)>,         // cases à réexaminer, par clé
+}
+
+fn distance(a: Cell, b: Cell) -> usize {
+    a.0.abs_diff(b.0).max(a.1.abs_diff(b.1))
+}
+
+impl DStarLite {
+    // Fonction pour préparer une recherche d'un départ vers une cible ; seul le coût de la
+    // cible est relevé
+    pub fn new(map: &Map, start: Cell, goal: Cell, cost_of: CostFn) -> Self {
+        let mut search = DStarLite {
+            width: map.width,
+            height: map.height,
+            goal,
+            start,
+            km: 0,
+            costs: HashMap::from([(goal, cost_of(map, goal))]),
+            g: HashMap::new(),
+            rhs: HashMap::from([(goal, 0)]),
+            queued: HashMap::new(),
+            open: BTreeSet::new(),
+        };
+        search.enqueue(goal);
+        search
+    }
+
+    // Fonction pour savoir si la recherche porte sur une carte de ces dimensions
+    pub fn fits(&self, map: &Map) -> bool {
+        (self.width, self.height) == (map.width, map.height)
+    }
+
+    // Fonction pour obtenir le coût relevé d'une case, None si elle est infranchissable ou
+    // n'a pas encore été examinée
+    pub fn cost(&self, cell: Cell) -> Option<usize> {
+        self.costs.get(&cell).copied().flatten()
+    }
+
+    // Fonction pour compter les cases dont la recherche garde l'état
+    pub fn visited(&self) -> usize {
+        self.costs.len()
+    }
+
+    // Coût relevé d'une case, relevé sur la carte à son premier examen
+    fn cost_at(&mut self, map: &Map, cell: Cell, cost_of: CostFn) -> Option<usize> {
+        *self.costs.entry(cell).or_insert_with(|| cost_of(map, cell))
+    }
+
+    fn g(&self, cell: Cell) -> usize {
+        self.g.get(&cell).copied().unwrap_or(INFINITY)
+    }
+
+    fn rhs(&self, cell: Cell) -> usize {
+        self.rhs.get(&cell).copied().unwrap_or(INFINITY)
+    }
+
+    fn key(&self, cell: Cell) -> Key {
+        let best = self.g(cell).min(self.rhs(cell));
+        (
+            best.saturating_add(distance(self.start, cell))
+                .saturating_add(self.km),
+            best,
+        )
+    }
+
+    fn enqueue(&mut self, cell: Cell) {
+        if let Some(key) = self.queued.remove(&cell) {
+            self.open.remove(&(key, cell));
+        }
+        if self.g(cell) != self.rhs(cell) {
+            let key = self.key(cell);
+            self.queued.insert(cell, key);
+            self.open.insert((key, cell));
+        }
+    }
+
+    // Coût prévu d'une case : le meilleur pas vers une voisine, plus le coût de cette voisine
+    // jusqu'à la cible. Le coût d'une voisine n'est relevé que si la cible est atteignable
+    // depuis elle.
+    fn update_vertex(&mut self, map: &Map, cell: Cell, cost_of: CostFn) {
+        if cell != self.goal {
+            let rhs = if self.cost_at(map, cell, cost_of).is_none() && cell != self.start {
+                INFINITY
+            } else {
+                let mut best = INFINITY;
+                for next in neighbors(map, cell) {
+                    let g = self.g(next);
+                    if g < best {
+                        if let Some(cost) = self.cost_at(map, next, cost_of) {
+                            best = best.min(cost.saturating_add(g));
+                        }
+                    }
+                }
+                best
+            };
+            if rhs == INFINITY {
+                self.rhs.remove(&cell);
+            } else {
+                self.rhs.insert(cell, rhs);
+            }
+        }
+        self.enqueue(cell);
+    }
+
+    // Fonction pour déplacer le départ de la recherche sur la nouvelle position du robot
+    pub fn move_to(&mut self, start: Cell) {
+        self.km += distance(self.start, start);
+        self.start = start;
+    }
+
+    // Fonction pour prendre en compte le nouveau coût de cases : seules leurs voisines, qui
+    // y entrent, sont réexaminées
+    pub fn update_costs(&mut self, map: &Map, cells: &[Cell], cost_of: CostFn) {
+        for &cell in cells {
+            self.costs.insert(cell, cost_of(map, cell));
+            for previous in neighbors(map, cell) {
+                self.update_vertex(map, previous, cost_of);
+            }
+        }
+    }
+
+    // Fonction pour mettre la recherche à jour jusqu'à connaître le coût du départ ; renvoie
+    // le nombre de cases développées
+    pub fn compute(&mut self, map: &Map, cost_of: CostFn) -> usize {
+        let mut expanded = 0;
+        while let Some(&(key, cell)) = self.open.first() {
+            if key >= self.key(self.start) && self.rhs(self.start) == self.g(self.start) {
+                break;
+            }
+            expanded += 1;
+            self.open.remove(&(key, cell));
+            self.queued.remove(&cell);
+            let current = self.key(cell);
+            if key < current {
+                self.queued.insert(cell, current);
+                self.open.insert((current, cell));
+            } else if self.g(cell) > self.rhs(cell) {
+                self.g.insert(cell, self.rhs(cell));
+                for previous in neighbors(map, cell) {
+                    self.update_vertex(map, previous, cost_of);
+                }
+            } else {
+                self.g.remove(&cell);
+                self.update_vertex(map, cell, cost_of);
+                for previous in neighbors(map, cell) {
+                    self.update_vertex(map, previous, cost_of);
+                }
+            }
+        }
+        expanded
+    }
+
+    // Fonction pour obtenir le chemin le moins coûteux du départ à la cible, en suivant à
+    // chaque pas la voisine la moins coûteuse ; vide si la cible est hors d'atteinte. Le
+    // chemin exclut le départ et inclut la cible.
+    pub fn path(&self, map: &Map) -> Vec<Cell> {
+        let mut path = Vec::new();
+        let mut cell = self.start;
+        while cell != self.goal {
+            let best = neighbors(map, cell)
+                .filter_map(|next| Some((self.cost(next)?.saturating_add(self.g(next)), next)))
+                .min_by_key(|&(cost, _)| cost);
+            match best {
+                Some((cost, next)) if cost < INFINITY && path.len() < self.g.len() => {
+                    path.push(next);
+                    cell = next;
+                }
+                _ => return Vec::new(),
+            }
+        }
+        path
+    }
+}
@@ -0,0 +1,78 @@
+// src/editor.rs
+use crate::resources::ResourceKind;
+use crate::terrain::Terrain;
+use crate::Map;
+
+// Outils disponibles dans le mode éditeur
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tool {
+    Obstacle,
+    Sand,
+    Rough,
+    Deposit(ResourceKind),
+    Base,
+}
+
+// Fonction pour appliquer un outil sur une case (clic gauche)
+pub fn paint(map: &mut Map, tool: Tool, cell: (usize, usize)) {
+    let (x, y) = cell;
+    if x >= map.width || y >= map.height {
+        return;
+    }
+    match tool {
+        Tool::Obstacle => {
+            if cell != map.base {
+                map.set_terrain(cell, Terrain::Rock);
+                map.clear_deposits(cell);
+            }
+        }
+        Tool::Sand => map.set_terrain(cell, Terrain::Sand),
+        Tool::Rough => map.set_terrain(cell, Terrain::Rough),
+        Tool::Deposit(kind) => {
+            if cell != map.base && kind.0 < map.resources.len() {
+                clear_rock(map, cell);
+                map.add_deposit(kind, cell);
+            }
+        }
+        Tool::Base => {
+            clear_rock(map, cell);
+            map.clear_deposits(cell);
+            map.base = cell;
+        }
+    }
+}
+
+// Fonction pour effacer ce que l'outil a posé sur une case (clic droit)
+pub fn erase(map: &mut Map, tool: Tool, cell: (usize, usize)) {
+    let (x, y) = cell;
+    if x >= map.width || y >= map.height {
+        return;
+    }
+    match tool {
+        Tool::Obstacle => clear_rock(map, cell),
+        Tool::Sand | Tool::Rough => {
+            if !map.is_obstacle((x, y)) {
+                map.set_terrain(cell, Terrain::Plain);
+            }
+        }
+        Tool::Deposit(kind) => {
+            if kind.0 < map.resources.len() {
+                map.remove_deposit(kind, cell);
+            }
+        }
+        // La base ne peut pas être supprimée, seulement déplacée
+        Tool::Base => {}
+    }
+}
+
+// Fonction pour rendre une case franchissable, le terrain non rocheux étant conservé
+fn clear_rock(map: &mut Map, cell: (usize, usize)) {
+    if map.is_obstacle(cell) {
+        map.set_terrain(cell, Terrain::Plain);
+    }
+}
+
+// Fonction pour révéler toute la carte pendant l'édition
+pub fn reveal(map: &mut Map) {
+    map.explore_all();
+}
@@ -0,0 +1,387 @@
+// src/export.rs
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Error, ErrorKind};
+use std::path::Path;
+
+// Image exportable : les pixels 0xAARRGGBB produits par render_map et leurs dimensions
+pub struct Frame {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u32>,
+}
+
+impl Frame {
+    // Fonction pour créer une image à partir d'un tampon de pixels
+    pub fn new(width: usize, height: usize, pixels: Vec<u32>) -> Self {
+        assert_eq!(pixels.len(), width * height, "taille du tampon incorrecte");
+        Frame {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    // Fonction pour agrandir l'image, chaque case devenant un carré de scale x scale pixels
+    pub fn scaled(&self, scale: usize) -> Frame {
+        let scale = scale.max(1);
+        let width = self.width * scale;
+        let height = self.height * scale;
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            let row = &self.pixels[(y / scale) * self.width..(y / scale + 1) * self.width];
+            for x in 0..width {
+                pixels.push(row[x / scale]);
+            }
+        }
+        Frame {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    fn rgb(&self) -> impl Iterator<Item = [u8; 3]> + '_ {
+        self.pixels
+            .iter()
+            .map(|&pixel| [(pixel >> 16) as u8, (pixel >> 8) as u8, pixel as u8])
+    }
+}
+
+// Fonction pour encoder une image au format PPM binaire (P6)
+pub fn encode_ppm(frame: &Frame) -> Vec<u8> {
+    let mut bytes = format!("P6\n{} {}\n255\n", frame.width, frame.height).into_bytes();
+    for rgb in frame.rgb() {
+        bytes.extend_from_slice(&rgb);
+    }
+    bytes
+}
+
+// Fonction pour encoder une image au format PNG (RGB 8 bits, sans compression)
+pub fn encode_png(frame: &Frame) -> Vec<u8> {
+    let mut bytes = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&(frame.width as u32).to_be_bytes());
+    header.extend_from_slice(&(frame.height as u32).to_be_bytes());
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+    png_chunk(&mut bytes, b"IHDR", &header);
+
+    // Chaque ligne est précédée du filtre 0 (aucun filtre)
+    let mut raw = Vec::with_capacity((frame.width * 3 + 1) * frame.height);
+    for row in frame.pixels.chunks(frame.width.max(1)) {
+        raw.push(0);
+        for &pixel in row {
+            raw.extend_from_slice(&[(pixel >> 16) as u8, (pixel >> 8) as u8, pixel as u8]);
+        }
+    }
+    png_chunk(&mut bytes, b"IDAT", &zlib_stored(&raw));
+    png_chunk(&mut bytes, b"IEND", &[]);
+    bytes
+}
+
+// Fonction pour enregistrer une image, le format étant choisi d'après l'extension (.png ou .ppm)
+pub fn save_frame(frame: &Frame, path: impl AsRef<Path>) -> io::Result<()> {
+    let path = path.as_ref();
+    let bytes = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("png") => encode_png(frame),
+        Some("ppm") => encode_ppm(frame),
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("format d'image inconnu : {}", path.display()),
+            ))
+        }
+    };
+    fs::write(path, bytes)
+}
+
+// Enregistreur d'images successives assemblées en GIF animé
+pub struct GifRecorder {
+    pub delay: u16, // délai entre deux images, en centièmes de seconde
+    frames: Vec<Frame>,
+}
+
+impl GifRecorder {
+    // Fonction pour créer un enregistreur vide
+    pub fn new(delay: u16) -> Self {
+        GifRecorder {
+            delay,
+            frames: Vec::new(),
+        }
+    }
+
+    // Fonction pour ajouter une image à l'animation
+    pub fn add_frame(&mut self, frame: Frame) {
+        if let Some(first) = self.frames.first() {
+            assert_eq!(
+                (first.width, first.height),
+                (frame.width, frame.height),
+                "toutes les images d'un GIF doivent avoir la même taille"
+            );
+        }
+        self.frames.push(frame);
+    }
+
+    // Fonction pour obtenir le nombre d'images enregistrées
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    // Fonction pour savoir si aucune image n'a été enregistrée
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    // Fonction pour encoder l'animation au format GIF89a, agrandie d'un facteur scale
+    pub fn encode(&self, scale: usize) -> Vec<u8> {
+        let scale = scale.max(1);
+        let (width, height) = self
+            .frames
+            .first()
+            .map_or((1, 1), |frame| (frame.width * scale, frame.height * scale));
+        let palette = Palette::new(&self.frames);
+
+        let mut bytes = b"GIF89a".to_vec();
+        bytes.extend_from_slice(&(width as u16).to_le_bytes());
+        bytes.extend_from_slice(&(height as u16).to_le_bytes());
+        // Table de couleurs globale de 256 entrées
+        bytes.extend_from_slice(&[0xF7, 0, 0]);
+        bytes.extend_from_slice(&palette.table());
+        // Extension NETSCAPE2.0 : l'animation boucle indéfiniment
+        bytes.extend_from_slice(&[0x21, 0xFF, 0x0B]);
+        bytes.extend_from_slice(b"NETSCAPE2.0");
+        bytes.extend_from_slice(&[0x03, 0x01, 0x00, 0x00, 0x00]);
+
+        for frame in &self.frames {
+            let frame = frame.scaled(scale);
+            bytes.extend_from_slice(&[0x21, 0xF9, 0x04, 0x00]);
+            bytes.extend_from_slice(&self.delay.to_le_bytes());
+            bytes.extend_from_slice(&[0x00, 0x00]);
+
+            bytes.push(0x2C);
+            bytes.extend_from_slice(&[0, 0, 0, 0]);
+            bytes.extend_from_slice(&(width as u16).to_le_bytes());
+            bytes.extend_from_slice(&(height as u16).to_le_bytes());
+            bytes.push(0x00);
+
+            let indices: Vec<u8> = frame
+                .pixels
+                .iter()
+                .map(|&pixel| palette.index(pixel))
+                .collect();
+            bytes.push(8);
+            for block in lzw_encode(&indices).chunks(255) {
+                bytes.push(block.len() as u8);
+                bytes.extend_from_slice(block);
+            }
+            bytes.push(0);
+        }
+
+        bytes.push(0x3B);
+        bytes
+    }
+
+    // Fonction pour enregistrer l'animation dans un fichier
+    pub fn save(&self, path: impl AsRef<Path>, scale: usize) -> io::Result<()> {
+        fs::write(path, self.encode(scale))
+    }
+}
+
+// Palette GIF : les couleurs exactes si elles tiennent en 256 entrées, sinon une palette RGB 3-3-2
+struct Palette {
+    exact: Option<HashMap<u32, u8>>,
+    colors: Vec<u32>,
+}
+
+impl Palette {
+    fn new(frames: &[Frame]) -> Self {
+        let mut exact = HashMap::new();
+        let mut colors = Vec::new();
+        for pixel in frames.iter().flat_map(|frame| frame.pixels.iter()) {
+            let color = pixel & 0x00FF_FFFF;
+            if let Entry::Vacant(entry) = exact.entry(color) {
+                if colors.len() == 256 {
+                    return Palette::rgb332();
+                }
+                entry.insert(colors.len() as u8);
+                colors.push(color);
+            }
+        }
+        Palette {
+            exact: Some(exact),
+            colors,
+        }
+    }
+
+    // Palette de repli lorsque les images comptent plus de 256 couleurs
+    fn rgb332() -> Self {
+        let colors = (0..=255u32)
+            .map(|index| {
+                let r = (index >> 5) * 255 / 7;
+                let g = ((index >> 2) & 0x07) * 255 / 7;
+                let b = (index & 0x03) * 255 / 3;
+                (r << 16) | (g << 8) | b
+            })
+            .collect();
+        Palette {
+            exact: None,
+            colors,
+        }
+    }
+
+    fn index(&self, pixel: u32) -> u8 {
+        match &self.exact {
+            Some(exact) => exact[&(pixel & 0x00FF_FFFF)],
+            None => {
+                let r = (pixel >> 21) & 0x07;
+                let g = (pixel >> 13) & 0x07;
+                let b = (pixel >> 6) & 0x03;
+                ((r << 5) | (g << 2) | b) as u8
+            }
+        }
+    }
+
+    fn table(&self) -> Vec<u8> {
+        let mut table = Vec::with_capacity(768);
+        for index in 0..256 {
+            let color = self.colors.get(index).copied().unwrap_or(0);
+            table.extend_from_slice(&[(color >> 16) as u8, (color >> 8) as u8, color as u8]);
+        }
+        table
+    }
+}
+
+// Fonction pour compresser des indices de palette avec l'algorithme LZW du format GIF
+fn lzw_encode(indices: &[u8]) -> Vec<u8> {
+    const CLEAR: u16 = 256;
+    const END: u16 = 257;
+    const MAX_CODES: u16 = 4096;
+
+    let mut writer = BitWriter::default();
+    let mut dictionary: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut code_size = 9;
+    let mut next_code = END + 1;
+
+    writer.write(CLEAR, code_size);
+    let mut current: Option<u16> = None;
+    for &index in indices {
+        let prefix = match current {
+            None => {
+                current = Some(index as u16);
+                continue;
+            }
+            Some(prefix) => prefix,
+        };
+        if let Some(&code) = dictionary.get(&(prefix, index)) {
+            current = Some(code);
+            continue;
+        }
+
+        writer.write(prefix, code_size);
+        if next_code < MAX_CODES {
+            // Le décodeur agrandit ses codes dès que le prochain code dépasse la taille courante
+            if next_code == 1 << code_size && code_size < 12 {
+                code_size += 1;
+            }
+            dictionary.insert((prefix, index), next_code);
+            next_code += 1;
+        } else {
+            writer.write(CLEAR, code_size);
+            dictionary.clear();
+            code_size = 9;
+            next_code = END + 1;
+        }
+        current = Some(index as u16);
+    }
+
+    if let Some(prefix) = current {
+        writer.write(prefix, code_size);
+        if next_code == 1 << code_size && code_size < 12 {
+            code_size += 1;
+        }
+    }
+    writer.write(END, code_size);
+    writer.finish()
+}
+
+// Ecriture de codes de taille variable, bits de poids faible en premier
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    count: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u32) {
+        self.buffer |= (code as u32) << self.count;
+        self.count += size;
+        while self.count >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+// Fonction pour ajouter un bloc PNG : longueur, type, données et CRC
+fn png_chunk(bytes: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    bytes.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = bytes.len();
+    bytes.extend_from_slice(kind);
+    bytes.extend_from_slice(data);
+    let crc = crc32(&bytes[start..]);
+    bytes.extend_from_slice(&crc.to_be_bytes());
+}
+
+// Fonction pour envelopper des données dans un flux zlib fait de blocs deflate non compressés
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![0x78, 0x01];
+    let mut blocks = data.chunks(0xFFFF).peekable();
+    if blocks.peek().is_none() {
+        bytes.extend_from_slice(&[0x01, 0x00, 0x00, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        bytes.push(if blocks.peek().is_none() { 0x01 } else { 0x00 });
+        let length = block.len() as u16;
+        bytes.extend_from_slice(&length.to_le_bytes());
+        bytes.extend_from_slice(&(!length).to_le_bytes());
+        bytes.extend_from_slice(block);
+    }
+    bytes.extend_from_slice(&adler32(data).to_be_bytes());
+    bytes
+}
+
+// Fonction pour calculer la somme de contrôle CRC-32 utilisée par PNG
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
@@ -0,0 +1,139 @@
+// src/frontier.rs
+use crate::bucket_grid::{manhattan, BucketGrid};
+use crate::Map;
+
+// Index des frontières de l'exploration : les cases à explorer voisines d'une case explorée,
+// rangées par paquets de cases, et le nombre de cases encore à explorer. Les cases hors
+// d'atteinte de la base comptent comme explorées. La case inexplorée la plus proche d'une case
+// explorée est toujours une frontière ; la chercher ne parcourt alors que les paquets proches
+// au lieu de toute la carte. L'index est tenu à jour à partir des seules cases découvertes,
+// isolées ou modifiées, sans jamais parcourir la carte.
+#[derive(Debug, Default, Clone)]
+pub struct FrontierIndex {
+    width: usize,
+    height: usize,
+    explored: Vec<bool>,   // cases explorées ou hors d'atteinte
+    unexplored: usize,     // nombre de cases à explorer
+    frontiers: BucketGrid, // frontières rangées par paquets
+    pub rebuilds: usize,   // reconstructions complètes
+}
+
+impl FrontierIndex {
+    // Fonction pour construire l'index d'une carte
+    pub fn new(map: &Map) -> Self {
+        let mut index = FrontierIndex::default();
+        index.rebuild(map);
+        index
+    }
+
+    // Fonction pour savoir si l'index porte sur une carte de ces dimensions
+    pub fn fits(&self, map: &Map) -> bool {
+        (self.width, self.height) == (map.width, map.height)
+    }
+
+    // Fonction pour reconstruire tout l'index
+    pub fn rebuild(&mut self, map: &Map) {
+        self.width = map.width;
+        self.height = map.height;
+        self.explored = map
+            .positions()
+            .map(|cell| !map.needs_exploration(cell))
+            .collect();
+        self.unexplored = self.explored.iter().filter(|&&explored| !explored).count();
+        self.frontiers = BucketGrid::new(map.width, map.height);
+        self.rebuilds += 1;
+        for cell in map.positions() {
+            self.refresh(map, cell);
+        }
+    }
+
+    fn index(&self, (x, y): (usize, usize)) -> usize {
+        y * self.width + x
+    }
+
+    // Range ou retire une case des frontières selon son état et celui de ses voisines
+    fn refresh(&mut self, map: &Map, (x, y): (usize, usize)) {
+        let frontier = !self.explored[self.index((x, y))]
+            && map
+                .neighbors((x, y))
+                .any(|next| self.explored[self.index(next)]);
+        if frontier {
+            self.frontiers.insert((x, y));
+        } else {
+            self.frontiers.remove((x, y));
+        }
+    }
+
+    // Fonction pour prendre en compte des cases qui viennent d'être explorées
+    pub fn update(&mut self, map: &Map, cells: impl IntoIterator<Item = (usize, usize)>) {
+        if !self.fits(map) {
+            self.rebuild(map);
+            return;
+        }
+        for cell in cells {
+            let index = self.index(cell);
+            match (self.explored[index], !map.needs_exploration(cell)) {
+                (false, true) => {}
+                (true, false) => {
+                    // Une case de nouveau à explorer (carte rechargée, roche effacée) : tout
+                    // est recalculé
+                    self.rebuild(map);
+                    return;
+                }
+                _ => continue,
+            }
+            self.explored[index] = true;
+            self.unexplored -= 1;
+            self.refresh(map, cell);
+            for next in map.neighbors(cell) {
+                self.refresh(map, next);
+            }
+        }
+    }
+
+    // Fonction pour prendre en compte les cases révélées autour d'une position
+    pub fn update_around(&mut self, map: &Map, (x, y): (usize, usize), range: usize) {
+        let cells = (y.saturating_sub(range)..=(y + range).min(map.height - 1)).flat_map(|y| {
+            (x.saturating_sub(range)..=(x + range).min(map.width - 1)).map(move |x| (x, y))
+        });
+        self.update(map, cells);
+    }
+
+    // Fonction pour obtenir le nombre de cases encore à explorer
+    pub fn unexplored(&self) -> usize {
+        self.unexplored
+    }
+
+    // Fonction pour savoir si toute la carte est explorée
+    pub fn is_complete(&self) -> bool {
+        self.unexplored == 0
+    }
+
+    // Fonction pour parcourir toutes les frontières
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.frontiers.cells()
+    }
+
+    // Fonction pour compter les cases à explorer d'une carte, par l'index s'il porte sur elle
+    pub fn count(&self, map: &Map) -> usize {
+        if self.fits(map) {
+            return self.unexplored;
+        }
+        map.positions()
+            .filter(|&cell| map.needs_exploration(cell))
+            .count()
+    }
+
+    // Fonction pour trouver la case à explorer la plus proche (distance de Manhattan), la
+    // première dans l'ordre des lignes à égalité. Seuls les paquets de frontières autour de
+    // la position sont parcourus.
+    pub fn nearest(&self, from: (usize, usize)) -> Option<(usize, usize)> {
+        if self.unexplored == 0 {
+            return None;
+        }
+        if !self.explored[self.index(from)] {
+            return Some(from);
+        }
+        self.frontiers.nearest(from, manhattan, |_| true)
+    }
+}
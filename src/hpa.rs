@@ -0,0 +1,443 @@
+// src/hpa.rs
+use crate::pathfinding::{neighbors, CostFn};
+use crate::Map;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap};
+
+// Côté d'un groupe de cases (cluster) de la hiérarchie
+pub const CLUSTER_SIZE: usize = 16;
+
+// Taille de carte à partir de laquelle les robots planifient avec la hiérarchie
+pub const HIERARCHY_MIN_SIZE: usize = 128;
+
+// Longueur d'ouverture au-delà de laquelle une frontière reçoit deux entrées au lieu d'une
+const WIDE_OPENING: usize = 6;
+
+// Zone rectangulaire de cases : colonnes x0..x1 et lignes y0..y1
+type Bounds = (usize, usize, usize, usize);
+
+// Frontière entre deux groupes voisins, désignés par leur colonne et leur ligne
+type Border = ((usize, usize), (usize, usize));
+
+// Entrée : deux cases face à face de part et d'autre d'une frontière
+type Entrance = ((usize, usize), (usize, usize));
+
+// Trajet vers une autre entrée du groupe, avec son coût
+type Edge = ((usize, usize), usize);
+
+// Planificateur hiérarchique (HPA*) : la carte est découpée en groupes de cases reliés par
+// des entrées placées sur leurs frontières. Un chemin est d'abord cherché d'entrée en entrée
+// dans ce graphe abstrait, puis détaillé groupe par groupe. Les groupes dont le terrain connu
+// change sont marqués à reconstruire ; ils le sont avant la recherche suivante.
+#[derive(Debug)]
+pub struct Hierarchy {
+    pub cluster_size: usize,
+    width: usize,
+    height: usize,
+    cost_of: CostFn,
+    known: Vec<u8>, // coût relevé de chaque case, 0 si infranchissable
+    borders: BTreeMap<Border, Vec<Entrance>>, // entrées de chaque frontière
+    links: HashMap<(usize, usize), Vec<(usize, usize)>>, // entrées voisines d'un autre groupe
+    edges: HashMap<(usize, usize), Vec<Edge>>, // trajets dans le groupe
+    nodes: HashMap<(usize, usize), Vec<(usize, usize)>>, // entrées de chaque groupe
+    dirty: BTreeSet<(usize, usize)>, // groupes à reconstruire
+    pub rebuilt: usize, // groupes reconstruits
+}
+
+// Coûts calculés dans une zone rectangulaire, rangés ligne par ligne
+struct Zone {
+    bounds: Bounds,
+    costs: Vec<Option<usize>>,
+}
+
+impl Zone {
+    fn new(bounds: Bounds) -> Self {
+        let (x0, y0, x1, y1) = bounds;
+        Zone {
+            bounds,
+            costs: vec![None; (x1 - x0) * (y1 - y0)],
+        }
+    }
+
+    fn index(&self, (x, y): (usize, usize)) -> usize {
+        let (x0, y0, x1, _) = self.bounds;
+        (y - y0) * (x1 - x0) + (x - x0)
+    }
+
+    fn get(&self, cell: (usize, usize)) -> Option<usize> {
+        if !contains(self.bounds, cell) {
+            return None;
+        }
+        self.costs[self.index(cell)]
+    }
+
+    fn set(&mut self, cell: (usize, usize), cost: usize) {
+        let index = self.index(cell);
+        self.costs[index] = Some(cost);
+    }
+}
+
+fn distance(a: (usize, usize), b: (usize, usize)) -> usize {
+    a.0.abs_diff(b.0).max(a.1.abs_diff(b.1))
+}
+
+fn contains((x0, y0, x1, y1): Bounds, (x, y): (usize, usize)) -> bool {
+    (x0..x1).contains(&x) && (y0..y1).contains(&y)
+}
+
+impl Hierarchy {
+    // Fonction pour construire la hiérarchie d'une carte, selon ce que les robots en savent
+    pub fn new(map: &Map) -> Self {
+        Hierarchy::with_cost(map, CLUSTER_SIZE, Map::known_cost)
+    }
+
+    // Fonction pour construire la hiérarchie avec une taille de groupe et une fonction de
+    // coût données
+    pub fn with_cost(map: &Map, cluster_size: usize, cost_of: CostFn) -> Self {
+        let mut hierarchy = Hierarchy {
+            cluster_size: cluster_size.max(2),
+            width: map.width,
+            height: map.height,
+            cost_of,
+            known: Vec::new(),
+            borders: BTreeMap::new(),
+            links: HashMap::new(),
+            edges: HashMap::new(),
+            nodes: HashMap::new(),
+            dirty: BTreeSet::new(),
+            rebuilt: 0,
+        };
+        hierarchy.known = (0..map.height)
+            .flat_map(|y| (0..map.width).map(move |x| (x, y)))
+            .map(|cell| hierarchy.code(map, cell))
+            .collect();
+        let (columns, rows) = hierarchy.clusters();
+        hierarchy.dirty = (0..rows)
+            .flat_map(|cy| (0..columns).map(move |cx| (cx, cy)))
+            .collect();
+        hierarchy.refresh(map);
+        hierarchy
+    }
+
+    fn code(&self, map: &Map, cell: (usize, usize)) -> u8 {
+        (self.cost_of)(map, cell).map_or(0, |cost| cost.min(u8::MAX as usize) as u8)
+    }
+
+    fn clusters(&self) -> (usize, usize) {
+        (
+            self.width.div_ceil(self.cluster_size),
+            self.height.div_ceil(self.cluster_size),
+        )
+    }
+
+    // Fonction pour obtenir le groupe d'une case
+    pub fn cluster_of(&self, (x, y): (usize, usize)) -> (usize, usize) {
+        (x / self.cluster_size, y / self.cluster_size)
+    }
+
+    fn bounds(&self, (cx, cy): (usize, usize)) -> Bounds {
+        let size = self.cluster_size;
+        (
+            cx * size,
+            cy * size,
+            ((cx + 1) * size).min(self.width),
+            ((cy + 1) * size).min(self.height),
+        )
+    }
+
+    // Frontières d'un groupe avec ses voisins directs
+    fn borders_of(&self, (cx, cy): (usize, usize)) -> Vec<Border> {
+        let (columns, rows) = self.clusters();
+        let around = [
+            (cx > 0).then(|| (cx - 1, cy)),
+            (cx + 1 < columns).then_some((cx + 1, cy)),
+            (cy > 0).then(|| (cx, cy - 1)),
+            (cy + 1 < rows).then_some((cx, cy + 1)),
+        ];
+        around
+            .into_iter()
+            .flatten()
+            .map(|other| ((cx, cy).min(other), (cx, cy).max(other)))
+            .collect()
+    }
+
+    // Fonction pour obtenir le nombre d'entrées du graphe abstrait
+    pub fn entrances(&self) -> usize {
+        self.nodes.values().map(Vec::len).sum()
+    }
+
+    // Fonction pour signaler des cases dont le terrain connu a pu changer : les groupes où
+    // le coût a vraiment changé sont marqués à reconstruire
+    pub fn invalidate(&mut self, map: &Map, cells: impl IntoIterator<Item = (usize, usize)>) {
+        for cell in cells {
+            let index = cell.1 * self.width + cell.0;
+            let code = self.code(map, cell);
+            if self.known[index] != code {
+                self.known[index] = code;
+                self.dirty.insert(self.cluster_of(cell));
+            }
+        }
+    }
+
+    // Fonction pour signaler les cases révélées autour d'une position
+    pub fn invalidate_around(&mut self, map: &Map, (x, y): (usize, usize), range: usize) {
+        let cells = (y.saturating_sub(range)..=(y + range).min(map.height - 1)).flat_map(|y| {
+            (x.saturating_sub(range)..=(x + range).min(map.width - 1)).map(move |x| (x, y))
+        });
+        self.invalidate(map, cells);
+    }
+
+    // Fonction pour reconstruire les groupes marqués : leurs frontières, puis les trajets
+    // entre entrées de ces groupes et de leurs voisins, dont les entrées ont pu changer
+    pub fn refresh(&mut self, map: &Map) {
+        if self.dirty.is_empty() {
+            return;
+        }
+        let dirty = std::mem::take(&mut self.dirty);
+        let mut borders = BTreeSet::new();
+        for &cluster in &dirty {
+            borders.extend(self.borders_of(cluster));
+        }
+        let mut touched = BTreeSet::new();
+        for border in borders {
+            touched.extend([border.0, border.1]);
+            self.rebuild_border(border);
+        }
+        touched.extend(dirty.iter().copied());
+        for &cluster in &touched {
+            self.rebuild_cluster(map, cluster);
+        }
+        self.rebuilt += dirty.len();
+    }
+
+    // Fonction pour placer les entrées d'une frontière entre deux groupes voisins : une par
+    // ouverture, deux aux extrémités d'une ouverture large
+    fn rebuild_border(&mut self, border: Border) {
+        for (a, b) in self.borders.remove(&border).unwrap_or_default() {
+            for (from, to) in [(a, b), (b, a)] {
+                if let Some(links) = self.links.get_mut(&from) {
+                    links.retain(|&cell| cell != to);
+                }
+            }
+        }
+        let (first, second) = border;
+        let (x0, y0, x1, y1) = self.bounds(first);
+        // Paires de cases face à face le long de la frontière
+        let pairs: Vec<Entrance> = if second.0 > first.0 {
+            (y0..y1).map(|y| ((x1 - 1, y), (x1, y))).collect()
+        } else {
+            (x0..x1).map(|x| ((x, y1 - 1), (x, y1))).collect()
+        };
+        let open = |&(a, b): &Entrance| self.cost(a).is_some() && self.cost(b).is_some();
+        let mut entrances = Vec::new();
+        let mut start = 0;
+        while start < pairs.len() {
+            if !open(&pairs[start]) {
+                start += 1;
+                continue;
+            }
+            let end = (start..pairs.len())
+                .find(|&i| !open(&pairs[i]))
+                .unwrap_or(pairs.len());
+            if end - start > WIDE_OPENING {
+                entrances.push(pairs[start]);
+                entrances.push(pairs[end - 1]);
+            } else {
+                entrances.push(pairs[(start + end - 1) / 2]);
+            }
+            start = end;
+        }
+        for &(a, b) in &entrances {
+            self.links.entry(a).or_default().push(b);
+            self.links.entry(b).or_default().push(a);
+        }
+        self.borders.insert(border, entrances);
+    }
+
+    // Fonction pour relier les entrées d'un groupe par les trajets les moins coûteux qui
+    // restent dans le groupe
+    fn rebuild_cluster(&mut self, map: &Map, cluster: (usize, usize)) {
+        for node in self.nodes.remove(&cluster).unwrap_or_default() {
+            self.edges.remove(&node);
+        }
+        let mut nodes: Vec<(usize, usize)> = self
+            .borders_of(cluster)
+            .iter()
+            .filter_map(|border| self.borders.get(border))
+            .flat_map(|pairs| pairs.iter().flat_map(|&(a, b)| [a, b]))
+            .filter(|&cell| self.cluster_of(cell) == cluster)
+            .collect();
+        nodes.sort();
+        nodes.dedup();
+        let bounds = self.bounds(cluster);
+        for &node in &nodes {
+            let costs = self.search(map, node, bounds, false);
+            let edges = nodes
+                .iter()
+                .filter(|&&other| other != node)
+                .filter_map(|&other| Some((other, costs.get(other)?)))
+                .collect();
+            self.edges.insert(node, edges);
+        }
+        self.nodes.insert(cluster, nodes);
+    }
+
+    // Coût relevé d'une case lors de la dernière reconstruction de son groupe
+    fn cost(&self, (x, y): (usize, usize)) -> Option<usize> {
+        match self.known[y * self.width + x] {
+            0 => None,
+            code => Some(code as usize),
+        }
+    }
+
+    // Recherche de Dijkstra limitée à une zone, depuis une case (coût pour aller de la case
+    // vers les autres) ou vers elle (coût pour aller des autres cases jusqu'à elle)
+    fn search(&self, map: &Map, from: (usize, usize), bounds: Bounds, towards: bool) -> Zone {
+        let mut zone = Zone::new(bounds);
+        let mut open = BinaryHeap::new();
+        zone.set(from, 0);
+        open.push(Reverse((0, from)));
+        while let Some(Reverse((cost, cell))) = open.pop() {
+            if zone.get(cell).is_some_and(|known| cost > known) {
+                continue;
+            }
+            // Vers la case, le pas coûte la case quittée ; depuis elle, la case d'arrivée
+            let backward = if towards { self.cost(cell) } else { Some(0) };
+            let Some(backward) = backward else {
+                continue;
+            };
+            for next in neighbors(map, cell).filter(|&next| contains(bounds, next)) {
+                let Some(forward) = self.cost(next) else {
+                    continue;
+                };
+                let step = if towards { backward } else { forward };
+                if zone.get(next).is_none_or(|known| cost + step < known) {
+                    zone.set(next, cost + step);
+                    open.push(Reverse((cost + step, next)));
+                }
+            }
+        }
+        zone
+    }
+
+    // Fonction pour trouver le chemin le moins coûteux entre deux cases dans une zone
+    fn local_path(
+        &self,
+        map: &Map,
+        from: (usize, usize),
+        to: (usize, usize),
+        bounds: Bounds,
+    ) -> Option<Vec<(usize, usize)>> {
+        let costs = self.search(map, to, bounds, true);
+        let mut path = Vec::new();
+        let mut cell = from;
+        while cell != to {
+            let remaining = costs.get(cell)?;
+            cell = neighbors(map, cell).find(|&next| {
+                costs
+                    .get(next)
+                    .zip(self.cost(next))
+                    .is_some_and(|(cost, entering)| cost + entering == remaining)
+            })?;
+            path.push(cell);
+        }
+        Some(path)
+    }
+
+    // Fonction pour trouver un chemin entre deux cases : dans le groupe si les deux cases y
+    // sont, sinon d'entrée en entrée puis détaillé. Le chemin exclut le départ et inclut
+    // l'arrivée ; None si aucun chemin n'existe. Les groupes marqués doivent avoir été
+    // reconstruits (refresh).
+    pub fn find_path(
+        &self,
+        map: &Map,
+        start: (usize, usize),
+        goal: (usize, usize),
+    ) -> Option<Vec<(usize, usize)>> {
+        self.cost(goal)?;
+        if start == goal {
+            return Some(Vec::new());
+        }
+        let (start_cluster, goal_cluster) = (self.cluster_of(start), self.cluster_of(goal));
+        if start_cluster == goal_cluster {
+            if let Some(path) = self.local_path(map, start, goal, self.bounds(start_cluster)) {
+                return Some(path);
+            }
+        }
+
+        // Accès du départ aux entrées de son groupe, et des entrées du groupe de l'arrivée
+        // jusqu'à elle
+        let exits = self.search(map, start, self.bounds(start_cluster), false);
+        let arrivals = self.search(map, goal, self.bounds(goal_cluster), true);
+        let empty = Vec::new();
+        let start_nodes = self.nodes.get(&start_cluster).unwrap_or(&empty);
+        let goal_nodes = self.nodes.get(&goal_cluster).unwrap_or(&empty);
+
+        // A* dans le graphe abstrait : le départ, les entrées, puis l'arrivée
+        let mut best: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut parent: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+        let mut open = BinaryHeap::new();
+        best.insert(start, 0);
+        open.push(Reverse((distance(start, goal), 0, start)));
+        while let Some(Reverse((_, cost, node))) = open.pop() {
+            if best.get(&node).is_some_and(|&known| cost > known) {
+                continue;
+            }
+            if node == goal {
+                break;
+            }
+            let mut next: Vec<Edge> = Vec::new();
+            if node == start {
+                next.extend(
+                    start_nodes
+                        .iter()
+                        .filter_map(|&entrance| Some((entrance, exits.get(entrance)?))),
+                );
+            }
+            if let Some(edges) = self.edges.get(&node) {
+                next.extend(edges.iter().copied());
+            }
+            if let Some(links) = self.links.get(&node) {
+                next.extend(
+                    links
+                        .iter()
+                        .filter_map(|&other| Some((other, self.cost(other)?))),
+                );
+            }
+            if goal_nodes.contains(&node) {
+                if let Some(arrival) = arrivals.get(node) {
+                    next.push((goal, arrival));
+                }
+            }
+            for (other, step) in next {
+                if best.get(&other).is_none_or(|&known| cost + step < known) {
+                    best.insert(other, cost + step);
+                    parent.insert(other, node);
+                    open.push(Reverse((
+                        cost + step + distance(other, goal),
+                        cost + step,
+                        other,
+                    )));
+                }
+            }
+        }
+        best.get(&goal)?;
+
+        // Détail du chemin : un pas entre deux groupes, un trajet local dans un groupe
+        let mut waypoints = vec![goal];
+        while let Some(&previous) = parent.get(waypoints.last().unwrap()) {
+            waypoints.push(previous);
+        }
+        waypoints.reverse();
+        let mut path = Vec::new();
+        for leg in waypoints.windows(2) {
+            let (from, to) = (leg[0], leg[1]);
+            if self.cluster_of(from) != self.cluster_of(to) {
+                path.push(to);
+            } else {
+                path.extend(self.local_path(map, from, to, self.bounds(self.cluster_of(to)))?);
+            }
+        }
+        Some(path)
+    }
+}
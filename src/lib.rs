@@ -1,40 +1,327 @@
-/*
 // src/lib.rs
-use minifb::{Window, WindowOptions};
+use action::{Action, ActionError};
+use base::Base;
+use behavior::{Behavior, Knowledge, Memory, RobotView};
+use clock::{shade, Clock};
+use deposit_index::DepositIndex;
+use minifb::Window;
+use modules::Modules;
 use noise::{NoiseFn, Perlin};
-use rand::Rng;
+use pathfinding::{find_safe_path, path_cost_with, CostFn, Route};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use resources::{ResourceKind, ResourceRegistry};
+use std::collections::{BTreeMap, HashMap};
 use std::time::{SystemTime, UNIX_EPOCH};
-use std::{thread, time};
+use terrain::Terrain;
+use traffic::Traffic;
 
-// Structure représentant la carte
+pub mod action;
+pub mod ascii_map;
+pub mod auction;
+pub mod base;
+pub mod behavior;
+pub mod bucket_grid;
+pub mod claims;
+pub mod clock;
+pub mod deposit_index;
+pub mod distance_field;
+pub mod dstar;
+pub mod editor;
+pub mod export;
+pub mod frontier;
+pub mod hpa;
+pub mod map_file;
+pub mod mapf;
+pub mod modules;
+pub mod pathfinding;
+pub mod resources;
+pub mod scheduler;
+pub mod script;
+pub mod terrain;
+pub mod tour;
+pub mod traffic;
+pub mod ui;
+
+// Capacité de la batterie standard d'un robot, rechargée entièrement à la base
+pub const BATTERY_CAPACITY: usize = 150;
+
+// Marge de batterie gardée en plus du coût du retour à la base
+pub const BATTERY_RESERVE: usize = 3;
+
+// Case de la carte : son terrain, son exploration et le gisement qu'elle porte
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cell {
+    pub terrain: Terrain,              // type de terrain (Rock pour les obstacles)
+    pub explored: bool,                // true si la case a été explorée, false sinon
+    pub unreachable: bool,             // case inexplorée que la roche connue isole de la base
+    pub deposit: Option<ResourceKind>, // gisement présent sur la case
+}
+
+// Structure représentant la carte. Les cases sont rangées ligne par ligne dans un seul
+// tableau ; les positions des gisements sont aussi listées et indexées par type de ressource.
 pub struct Map {
-    pub width: usize,
-    pub height: usize,
-    pub obstacles: Vec<Vec<bool>>,     // true si obstacle, false sinon
-    pub energy: Vec<(usize, usize)>,   // positions des sources d'énergie
-    pub minerals: Vec<(usize, usize)>, // positions des gisements de minerais
-    pub base: (usize, usize),          // position de la base
-    pub explored: Vec<Vec<bool>>,
+    pub width: usize,                              // largeur de la carte
+    pub height: usize,                             // hauteur de la carte
+    cells: Vec<Cell>,                              // cases de la carte, ligne par ligne
+    pub resources: ResourceRegistry,               // types de ressources présents sur la carte
+    deposits: Vec<Vec<(usize, usize)>>,            // positions des gisements de chaque type
+    deposit_slots: HashMap<(usize, usize), usize>, // place de chaque gisement dans sa liste
+    deposit_index: Vec<DepositIndex>,              // index spatial des gisements de chaque type
+    pub depleted: Vec<Depleted>,                   // gisements renouvelables épuisés, en attente
+    pub base: (usize, usize),                      // position de la base
+    revision: usize,                               // changements qui peuvent baisser un coût connu
+}
+
+// Gisement renouvelable épuisé, qui réapparaît après un certain nombre de tours
+#[derive(Debug, Clone, PartialEq)]
+pub struct Depleted {
+    pub kind: ResourceKind,   // type de ressource du gisement
+    pub cell: (usize, usize), // position du gisement
+    pub remaining: usize,     // tours restants avant sa réapparition
+}
+
+impl Map {
+    // Fonction pour obtenir l'indice d'une case dans le tableau des cases
+    pub fn index(&self, (x, y): (usize, usize)) -> usize {
+        y * self.width + x
+    }
+
+    // Fonction pour obtenir une case
+    pub fn cell(&self, position: (usize, usize)) -> &Cell {
+        &self.cells[self.index(position)]
+    }
+
+    // Fonction pour obtenir toutes les cases, ligne par ligne
+    pub fn cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    // Fonction pour obtenir les cases d'une ligne
+    pub fn row(&self, y: usize) -> &[Cell] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    // Fonction pour parcourir les positions de toutes les cases, ligne par ligne
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    // Fonction pour parcourir les voisines d'une case, diagonales comprises
+    pub fn neighbors(&self, (x, y): (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+        pathfinding::DIRECTIONS.iter().filter_map(move |&(dx, dy)| {
+            let x = x.checked_add_signed(dx)?;
+            let y = y.checked_add_signed(dy)?;
+            (x < width && y < height).then_some((x, y))
+        })
+    }
+
+    // Fonction pour obtenir le terrain d'une case
+    pub fn terrain_at(&self, position: (usize, usize)) -> Terrain {
+        self.cell(position).terrain
+    }
+
+    // Fonction pour savoir si une case est un obstacle
+    pub fn is_obstacle(&self, position: (usize, usize)) -> bool {
+        self.cell(position).terrain == Terrain::Rock
+    }
+
+    // Fonction pour savoir si une case a été explorée
+    pub fn is_explored(&self, position: (usize, usize)) -> bool {
+        self.cell(position).explored
+    }
+
+    // Fonction pour savoir si une case inexplorée est isolée de la base par la roche connue
+    pub fn is_unreachable(&self, position: (usize, usize)) -> bool {
+        self.cell(position).unreachable
+    }
+
+    // Fonction pour savoir si une case reste à explorer : ni explorée, ni hors d'atteinte
+    pub fn needs_exploration(&self, position: (usize, usize)) -> bool {
+        let cell = self.cell(position);
+        !cell.explored && !cell.unreachable
+    }
+
+    // Fonction pour marquer une case explorée ou non
+    pub fn set_explored(&mut self, position: (usize, usize), explored: bool) {
+        let index = self.index(position);
+        if self.cells[index].explored && !explored {
+            self.revision += 1;
+        }
+        self.cells[index].explored = explored;
+    }
+
+    // Fonction pour marquer une case inexplorée isolée de la base ou de nouveau atteignable
+    pub fn set_unreachable(&mut self, position: (usize, usize), unreachable: bool) {
+        let index = self.index(position);
+        self.cells[index].unreachable = unreachable;
+    }
+
+    // Fonction pour marquer toute la carte explorée
+    pub fn explore_all(&mut self) {
+        for cell in &mut self.cells {
+            cell.explored = true;
+        }
+    }
+
+    // Fonction pour obtenir les gisements d'un type de ressource ; un gisement retiré est
+    // remplacé dans la liste par le dernier
+    pub fn deposits(&self, kind: ResourceKind) -> &[(usize, usize)] {
+        &self.deposits[kind.0]
+    }
+
+    // Fonction pour obtenir l'index spatial des gisements d'un type de ressource
+    pub fn deposit_index(&self, kind: ResourceKind) -> &DepositIndex {
+        &self.deposit_index[kind.0]
+    }
+
+    // Fonction pour trouver le gisement accepté d'un type de ressource le plus proche d'une
+    // case (distance de Manhattan)
+    pub fn nearest_deposit(
+        &self,
+        kind: ResourceKind,
+        from: (usize, usize),
+        accept: impl Fn((usize, usize)) -> bool,
+    ) -> Option<(usize, usize)> {
+        self.deposit_index[kind.0].nearest(from, accept)
+    }
+
+    // Fonction pour trouver le gisement accepté d'un type de ressource le moins coûteux à
+    // atteindre depuis une case par un chemin d'au plus `limit`, avec le coût du chemin
+    pub fn nearest_deposit_by_path(
+        &self,
+        kind: ResourceKind,
+        from: (usize, usize),
+        cost_of: CostFn,
+        limit: usize,
+        accept: impl Fn((usize, usize)) -> bool,
+    ) -> Option<((usize, usize), usize)> {
+        self.deposit_index[kind.0].nearest_by_path(self, kind, from, cost_of, limit, accept)
+    }
+
+    // Fonction pour ajouter un gisement, une case ne portant qu'un seul gisement
+    pub fn add_deposit(&mut self, kind: ResourceKind, cell: (usize, usize)) {
+        self.clear_deposits(cell);
+        self.deposit_slots.insert(cell, self.deposits[kind.0].len());
+        self.deposits[kind.0].push(cell);
+        self.deposit_index[kind.0].insert(cell);
+        let index = self.index(cell);
+        self.cells[index].deposit = Some(kind);
+    }
+
+    // Fonction pour retirer un gisement épuisé
+    pub fn remove_deposit(&mut self, kind: ResourceKind, cell: (usize, usize)) {
+        let index = self.index(cell);
+        if self.cells[index].deposit == Some(kind) {
+            self.cells[index].deposit = None;
+            if let Some(slot) = self.deposit_slots.remove(&cell) {
+                let deposits = &mut self.deposits[kind.0];
+                deposits.swap_remove(slot);
+                if let Some(&moved) = deposits.get(slot) {
+                    self.deposit_slots.insert(moved, slot);
+                }
+            }
+            self.deposit_index[kind.0].remove(cell);
+        }
+    }
+
+    // Fonction pour épuiser un gisement collecté : il réapparaîtra plus tard si la ressource
+    // est renouvelable
+    pub fn deplete(&mut self, kind: ResourceKind, cell: (usize, usize)) {
+        self.remove_deposit(kind, cell);
+        let regeneration = self.resources.get(kind).regeneration;
+        if regeneration > 0 {
+            self.depleted.push(Depleted {
+                kind,
+                cell,
+                remaining: regeneration,
+            });
+        }
+    }
+
+    // Fonction pour retirer tous les gisements d'une case, y compris ceux en régénération
+    pub fn clear_deposits(&mut self, cell: (usize, usize)) {
+        if let Some(kind) = self.deposit_at(cell) {
+            self.remove_deposit(kind, cell);
+        }
+        self.depleted.retain(|depleted| depleted.cell != cell);
+    }
+
+    // Fonction pour obtenir le type du gisement présent sur une case
+    pub fn deposit_at(&self, cell: (usize, usize)) -> Option<ResourceKind> {
+        self.cell(cell).deposit
+    }
+
+    // Fonction pour changer le terrain d'une case ; la roche en fait un obstacle
+    pub fn set_terrain(&mut self, cell: (usize, usize), terrain: Terrain) {
+        let index = self.index(cell);
+        if self.cells[index].terrain != terrain {
+            self.cells[index].terrain = terrain;
+            self.revision += 1;
+        }
+    }
+
+    // Fonction pour obtenir la révision de la carte. Découvrir des cases ne fait que monter les
+    // coûts connus ; la révision augmente quand un coût connu peut baisser (terrain modifié,
+    // case redevenue inconnue), ce qui périme les distances calculées auparavant.
+    pub fn revision(&self) -> usize {
+        self.revision
+    }
+
+    // Fonction pour obtenir le coût réel pour entrer sur une case, None si infranchissable
+    pub fn movement_cost(&self, cell: (usize, usize)) -> Option<usize> {
+        self.terrain_at(cell).cost()
+    }
+
+    // Fonction pour obtenir le coût connu des robots : une case inexplorée est supposée en plaine
+    pub fn known_cost(&self, cell: (usize, usize)) -> Option<usize> {
+        let cell = self.cell(cell);
+        if cell.explored {
+            cell.terrain.cost()
+        } else {
+            Terrain::Plain.cost()
+        }
+    }
+
+    // Fonction pour obtenir un coût prudent : une case inexplorée est supposée accidentée
+    pub fn safe_cost(&self, cell: (usize, usize)) -> Option<usize> {
+        let cell = self.cell(cell);
+        if cell.explored {
+            cell.terrain.cost()
+        } else {
+            Terrain::Rough.cost()
+        }
+    }
 }
 
 // Structure représentant un robot
 pub struct Robot {
-    pub x: usize,
-    pub y: usize,
-    pub energy: usize,
-    pub minerals: usize,
-    pub task: Task,
-    pub state: RobotState,
+    pub x: usize,                            // position x
+    pub y: usize,                            // position y
+    pub load: BTreeMap<ResourceKind, usize>, // quantités transportées, par type de ressource
+    pub task: Task,                          // tâche actuelle
+    pub state: RobotState,                   // état actuel
+    pub route: Route,                        // cible actuelle et chemin calculé pour l'atteindre
+    pub tour: Vec<(usize, usize)>,           // gisements restant à visiter avant de rentrer
+    pub goal: Option<(usize, usize)>,        // case à explorer attribuée par la base
+    pub battery: usize,                      // charge restante de la batterie
+    pub modules: Modules,                    // modules montés sur le châssis
+    pub drill_progress: usize,               // tours de forage ou d'analyse déjà passés sur place
+    pub blocked: usize,                      // tours passés bloqué par un autre robot
+    pub behavior: Option<Box<dyn Behavior>>, // stratégie personnalisée, sinon celle de la tâche
 }
 
-// Enumération des tâches des robots
-#[derive(PartialEq)] // Ajout du dérive pour PartialEq
+// Enumération des tâches possibles pour un robot
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Task {
-    CollectEnergy,
-    CollectMinerals,
+    Collect(ResourceKind),
     Explore,
 }
 
+// Enumération des états possibles pour un robot
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum RobotState {
     Exploring,
     Returning,
@@ -42,41 +329,170 @@ pub enum RobotState {
 }
 
 impl Robot {
-    // Fonction pour créer un nouveau robot avec des valeurs initiales
+    // Fonction pour créer un nouveau robot à l'équipement standard
     pub fn new(x: usize, y: usize, task: Task) -> Self {
+        Robot::with_modules(x, y, task, Modules::standard())
+    }
+
+    // Fonction pour créer un nouveau robot avec des modules donnés
+    pub fn with_modules(x: usize, y: usize, task: Task, modules: Modules) -> Self {
         Robot {
             x,
             y,
-            energy: 0,
-            minerals: 0,
+            load: BTreeMap::new(),
             task,
             state: RobotState::Exploring,
+            route: Route::default(),
+            tour: Vec::new(),
+            goal: None,
+            battery: modules.battery_capacity(),
+            modules,
+            drill_progress: 0,
+            blocked: 0,
+            behavior: None,
         }
     }
 
-    pub fn move_towards(&mut self, target: (usize, usize)) {
-        let dx = if self.x < target.0 {
-            1
-        } else if self.x > target.0 {
-            -1
-        } else {
-            0
-        };
-        let dy = if self.y < target.1 {
-            1
-        } else if self.y > target.1 {
-            -1
-        } else {
-            0
+    // Fonction pour obtenir le nombre d'unités transportées
+    pub fn cargo(&self) -> usize {
+        self.load.values().sum()
+    }
+
+    // Fonction pour obtenir la quantité transportée d'un type de ressource
+    pub fn carried(&self, kind: ResourceKind) -> usize {
+        self.load.get(&kind).copied().unwrap_or(0)
+    }
+
+    // Fonction pour obtenir le nombre de tours passés sur place par unité collectée, selon le
+    // module qu'exige la ressource de sa tâche
+    pub(crate) fn work_ticks(&self, map: &Map) -> Option<usize> {
+        match self.task {
+            Task::Collect(kind) => self.modules.work_ticks(map.resources.get(kind).extraction),
+            Task::Explore => None,
+        }
+    }
+
+    // Fonction pour savoir si le robot peut collecter : il lui faut l'outil de sa tâche et une soute
+    pub fn can_collect(&self, map: &Map) -> bool {
+        self.work_ticks(map).is_some() && self.modules.cargo_capacity() > 0
+    }
+
+    // Fonction pour forer ou analyser un tour sur place, true quand une unité est collectée
+    pub fn drill(&mut self, map: &Map) -> bool {
+        let Some(ticks) = self.work_ticks(map) else {
+            return false;
         };
-        let new_x = (self.x as isize).wrapping_add(dx as isize) as usize;
-        let new_y = (self.y as isize).wrapping_add(dy as isize) as usize;
+        self.drill_progress += 1;
+        if self.drill_progress < ticks {
+            return false;
+        }
+        self.drill_progress = 0;
+        true
+    }
+
+    // Fonction pour déplacer le robot vers une position cible, en ligne droite
+    pub fn move_towards(&mut self, target: (usize, usize)) {
+        self.route.target = Some(target);
+        self.route.path.clear();
+        let (new_x, new_y) = step_towards((self.x, self.y), target);
         self.x = new_x;
         self.y = new_y;
     }
+
+    // Fonction pour avancer d'une case vers une cible en suivant le chemin le moins coûteux.
+    // Le déplacement consomme la batterie selon le terrain ; renvoie false si aucun chemin
+    // connu n'existe. Un obstacle découvert en chemin est marqué exploré et le robot attend.
+    pub fn travel_to(&mut self, map: &mut Map, target: (usize, usize)) -> bool {
+        self.travel(map, target, Map::known_cost)
+    }
+
+    // Fonction pour avancer d'une case vers la base par un chemin prudent, dont le coût réel
+    // ne dépasse pas celui estimé par needs_recharge
+    pub fn return_to_base(&mut self, map: &mut Map) -> bool {
+        let base = map.base;
+        self.travel(map, base, Map::safe_cost)
+    }
+
+    // Fonction pour suivre l'itinéraire choisi par la stratégie du robot, prudent au retour
+    fn follow_route(&mut self, map: &mut Map) {
+        if let Some(target) = self.route.target {
+            let cost_of: CostFn = match self.state {
+                RobotState::Returning => Map::safe_cost,
+                _ => Map::known_cost,
+            };
+            self.travel(map, target, cost_of);
+        }
+    }
+
+    // Les roues permettent de parcourir plusieurs cases par tour
+    fn travel(&mut self, map: &mut Map, target: (usize, usize), cost_of: CostFn) -> bool {
+        for _ in 0..self.modules.steps_per_tick() {
+            let from = (self.x, self.y);
+            let Some(direction) = self.route.next_step(map, from, target, cost_of) else {
+                return from == target;
+            };
+            match action::apply_move(map, &Traffic::default(), self, direction) {
+                Ok(()) => {}
+                Err(ActionError::Obstacle((x, y))) => {
+                    map.set_explored((x, y), true);
+                    break;
+                }
+                Err(_) => break,
+            }
+        }
+        true
+    }
+
+    // Fonction pour savoir si le robot doit rentrer recharger sa batterie
+    pub fn needs_recharge(&self, map: &Map) -> bool {
+        match find_safe_path(map, (self.x, self.y), map.base) {
+            Some(path) => {
+                self.battery <= path_cost_with(map, &path, Map::safe_cost) + BATTERY_RESERVE
+            }
+            None => false,
+        }
+    }
+
+    // Fonction pour obtenir le chemin prévu jusqu'à la cible actuelle
+    pub fn planned_path(&self) -> Vec<(usize, usize)> {
+        if !self.route.path.is_empty() {
+            return self.route.path.clone();
+        }
+        let mut path = Vec::new();
+        if let Some(target) = self.route.target {
+            let mut position = (self.x, self.y);
+            while position != target {
+                position = step_towards(position, target);
+                path.push(position);
+            }
+        }
+        path
+    }
+}
+
+// Fonction pour calculer la case suivante d'un déplacement vers une cible
+fn step_towards(from: (usize, usize), target: (usize, usize)) -> (usize, usize) {
+    let dx = if from.0 < target.0 {
+        1
+    } else if from.0 > target.0 {
+        -1
+    } else {
+        0
+    };
+    let dy = if from.1 < target.1 {
+        1
+    } else if from.1 > target.1 {
+        -1
+    } else {
+        0
+    };
+    let new_x = (from.0 as isize).wrapping_add(dx) as usize;
+    let new_y = (from.1 as isize).wrapping_add(dy) as usize;
+    (new_x, new_y)
 }
 
-pub fn draw_map(window: &mut Window, map: &Map, robots: &[Robot]) {
+// Fonction pour calculer l'image de la carte et des robots
+pub fn render_map(map: &Map, robots: &[Robot]) -> Vec<u32> {
     let mut buffer: Vec<u32> = vec![0; map.width * map.height];
     for y in 0..map.height {
         for x in 0..map.width {
@@ -89,22 +505,12 @@ pub fn draw_map(window: &mut Window, map: &Map, robots: &[Robot]) {
                     && x <= map.base.0.saturating_add(1))
             {
                 buffer[index] = 0xFF_00FFFF;
-            } else if map.explored[y][x] {
-                if map.obstacles[y][x] {
-                    buffer[index] = 0xFF_000000;
-                } else if map.energy.contains(&(x, y)) || map.minerals.contains(&(x, y)) {
-                    if !map.explored[y][x] {
-                        buffer[index] = 0xFF_AAAAAA;
-                    } else {
-                        buffer[index] = if map.energy.contains(&(x, y)) {
-                            0xFF_00FF00
-                        } else {
-                            0xFFFF0000
-                        };
-                    }
-                } else {
-                    buffer[index] = 0xFF_FFFFFF;
-                }
+            } else if map.is_explored((x, y)) {
+                let cell = map.cell((x, y));
+                buffer[index] = match cell.deposit {
+                    Some(kind) if cell.terrain != Terrain::Rock => map.resources.get(kind).color,
+                    _ => cell.terrain.color(),
+                };
             } else {
                 buffer[index] = 0xFF_AAAAAA;
             }
@@ -121,160 +527,393 @@ pub fn draw_map(window: &mut Window, map: &Map, robots: &[Robot]) {
         buffer[index] = color;
     }
 
+    buffer
+}
+
+// Fonction pour afficher une image de la carte dans la fenêtre
+pub fn present(window: &mut Window, map: &Map, buffer: &[u32]) {
     window
-        .update_with_buffer(&buffer, map.width, map.height)
+        .update_with_buffer(buffer, map.width, map.height)
         .unwrap();
 }
 
-pub fn explore_map(robot: &mut Robot, map: &mut Map) {
-    let mut target = (robot.x, robot.y);
-    let mut min_distance = isize::MAX;
+// Fonction pour calculer l'image de la scène, assombrie selon l'heure
+pub fn render_scene(map: &Map, robots: &[Robot], clock: &Clock) -> Vec<u32> {
+    let mut buffer = render_map(map, robots);
+    shade(&mut buffer, clock.light());
+    buffer
+}
 
-    for y in 0..map.height {
-        for x in 0..map.width {
-            if !map.explored[y][x] {
-                let distance = ((robot.x as isize - x as isize).abs()
-                    + (robot.y as isize - y as isize).abs())
-                    as isize;
-                if distance < min_distance {
-                    min_distance = distance;
-                    target = (x, y);
+// Fonction pour dessiner la carte et les robots
+pub fn draw_map(window: &mut Window, map: &Map, robots: &[Robot], clock: &Clock) {
+    let buffer = render_scene(map, robots, clock);
+    present(window, map, &buffer);
+}
+
+// Fonction pour explorer la carte avec la portée du capteur du robot à l'heure de l'horloge
+pub fn explore_map(robot: &mut Robot, map: &mut Map, base: &mut Base, clock: &Clock) {
+    let range = clock.sensor_range(robot.modules.sensor_range());
+    explore_with_range(robot, map, base, range, clock);
+}
+
+// Fonction pour explorer la carte : le robot vise la case inexplorée la plus proche, trouvée
+// dans l'index des frontières de la base, révèle les cases à `range` cases autour de lui puis
+// avance vers sa cible. La base répare ses structures à partir des cases révélées, ce qui
+// repère au passage les cases que la roche isole.
+pub fn explore_with_range(
+    robot: &mut Robot,
+    map: &mut Map,
+    base: &mut Base,
+    range: usize,
+    clock: &Clock,
+) {
+    base.prepare(map);
+    let view = RobotView::of(robot, map, range);
+    let mut memory = lend_memory(robot);
+    let knowledge = Knowledge::new(map, clock)
+        .with_home(&base.home)
+        .with_frontier(&base.frontier);
+    let action = behavior::explore(&mut memory, &view, &knowledge);
+    restore_memory(robot, memory);
+    reveal_around(map, (robot.x, robot.y), range);
+    base.revealed(map, (robot.x, robot.y), range);
+    if let Action::Move(_) = action {
+        robot.follow_route(map);
+    }
+}
+
+// Fonction pour révéler les cases à `range` cases autour d'une position
+pub fn reveal_around(map: &mut Map, (x, y): (usize, usize), range: usize) {
+    for y in y.saturating_sub(range)..=(y + range).min(map.height - 1) {
+        for x in x.saturating_sub(range)..=(x + range).min(map.width - 1) {
+            map.set_explored((x, y), true);
+        }
+    }
+}
+
+// Fonction pour collecter des ressources : le robot va au gisement connu le plus proche du
+// type de sa tâche, le fore ou l'analyse puis rentre à la base quand sa soute est pleine
+pub fn collect_resources(robot: &mut Robot, map: &mut Map, clock: &Clock) {
+    let range = clock.sensor_range(robot.modules.sensor_range());
+    let view = RobotView::of(robot, map, range);
+    let mut memory = lend_memory(robot);
+    let action = behavior::collect(&mut memory, &view, &Knowledge::new(map, clock));
+    restore_memory(robot, memory);
+    match action {
+        Action::Move(_) => robot.follow_route(map),
+        Action::Mine => {
+            let _ = action::apply_mine(map, robot);
+        }
+        _ => {}
+    }
+}
+
+// Fonction pour prêter l'état, l'itinéraire et la tournée d'un robot aux stratégies intégrées
+pub(crate) fn lend_memory(robot: &mut Robot) -> Memory {
+    Memory {
+        state: robot.state,
+        route: std::mem::take(&mut robot.route),
+        tour: std::mem::take(&mut robot.tour),
+        goal: robot.goal,
+    }
+}
+
+// Fonction pour rendre au robot la mémoire prêtée
+pub(crate) fn restore_memory(robot: &mut Robot, memory: Memory) {
+    robot.state = memory.state;
+    robot.route = memory.route;
+    robot.tour = memory.tour;
+    robot.goal = memory.goal;
+}
+
+// Fonction pour faire repousser les gisements renouvelables épuisés. Les ressources solaires
+// ne se régénèrent qu'en plein jour.
+pub fn regenerate_deposits(map: &mut Map, daylight: bool) {
+    let resources = &map.resources;
+    let mut regrown = Vec::new();
+    map.depleted.retain_mut(|depleted| {
+        if daylight || !resources.get(depleted.kind).solar {
+            depleted.remaining = depleted.remaining.saturating_sub(1);
+        }
+        if depleted.remaining == 0 {
+            regrown.push((depleted.kind, depleted.cell));
+            false
+        } else {
+            true
+        }
+    });
+    for (kind, cell) in regrown {
+        if map.deposit_at(cell).is_none() {
+            map.add_deposit(kind, cell);
+        }
+    }
+}
+
+// Fonction pour faire jouer un robot : il observe les alentours et choisit son action selon
+// sa stratégie ; la simulation la valide puis l'applique. Les roues permettent d'enchaîner les
+// déplacements dans le même tour.
+fn act(map: &mut Map, robot: &mut Robot, index: usize, base: &mut Base, clock: &Clock) {
+    let range = sense(robot, clock);
+    for _ in 0..robot.modules.steps_per_tick() {
+        let action = choose(map, robot, index, base, clock, range);
+        match action::apply(map, robot, base, action) {
+            Ok(()) => {
+                service(map, robot, base, action);
+                robot.blocked = 0;
+                if matches!(action, Action::Move(_)) {
+                    continue;
                 }
             }
+            // Un obstacle découvert au contact est porté sur la carte
+            Err(ActionError::Obstacle((x, y))) => {
+                map.set_explored((x, y), true);
+                base.edited(map, &[(x, y)]);
+            }
+            // Un robot bloqué par un autre attend, puis finit par s'écarter
+            Err(ActionError::Occupied(cell)) => base.traffic.blocked(map, robot, cell),
+            Err(_) => {}
         }
+        break;
+    }
+}
+
+// Fonction pour faire jouer la flotte avec le planificateur commun : tous les robots
+// choisissent leur action, le planificateur rend leurs déplacements compatibles, puis la
+// simulation applique les actions. Chaque robot avance alors d'une case au plus par tour.
+fn act_planned(map: &mut Map, robots: &mut [Robot], base: &mut Base, clock: &Clock) {
+    let mut actions: Vec<Action> = robots
+        .iter_mut()
+        .enumerate()
+        .map(|(index, robot)| {
+            let range = sense(robot, clock);
+            choose(map, robot, index, base, clock, range)
+        })
+        .collect();
+    let wanted: Vec<bool> = actions
+        .iter()
+        .map(|action| matches!(action, Action::Move(_)))
+        .collect();
+    let decided = actions.clone();
+    if let Some(fleet) = base.traffic.fleet.as_mut() {
+        fleet.coordinate(map, robots, &mut actions);
     }
 
-    for dy in -1..=1 {
-        for dx in -1..=1 {
-            let x = (robot.x as isize + dx) as usize;
-            let y = (robot.y as isize + dy) as usize;
-            if x < map.width && y < map.height {
-                map.explored[y][x] = true;
+    // Un déplacement remplacé par une attente compte comme refusé faute de place
+    for (robot, (decided, action)) in robots.iter().zip(decided.iter().zip(&actions)) {
+        if let (Action::Move(direction), Action::Wait) = (decided, action) {
+            if let Some(cell) = direction.step(map, (robot.x, robot.y)) {
+                base.traffic.stats.waits += 1;
+                *base.traffic.stats.jams.entry(cell).or_insert(0) += 1;
             }
         }
     }
 
-    robot.move_towards(target);
-}
-pub fn collect_resources(robot: &mut Robot, map: &mut Map) {
-    match robot.task {
-        Task::CollectEnergy => {
-            if let Some(&(x, y)) = map
-                .energy
-                .iter()
-                .find(|&&(ex, ey)| ex == robot.x && ey == robot.y)
-            {
-                robot.energy += 1;
-                map.energy.retain(|&pos| pos != (x, y));
-                robot.state = RobotState::Returning;
-            } else {
-                if let Some(target) = map
-                    .energy
-                    .iter()
-                    .min_by_key(|&&(x, y)| {
-                        ((robot.x as isize - x as isize).abs() + (robot.y as isize - y as isize).abs())
-                            as isize
-                    })
-                    .cloned()
-                {
-                    robot.move_towards(target);
-                } else {
-                    robot.state = RobotState::Returning;
+    // Un déplacement vers une case que son occupant quitte dans le même tour est retenté une
+    // fois les autres robots passés
+    let mut pending: Vec<usize> = (0..robots.len()).collect();
+    loop {
+        let mut blocked = Vec::new();
+        for &index in &pending {
+            let robot = &mut robots[index];
+            let action = actions[index];
+            match action::apply(map, robot, base, action) {
+                Ok(()) if !wanted[index] || matches!(action, Action::Move(_)) => {
+                    service(map, robot, base, action);
+                    robot.blocked = 0;
                 }
+                Ok(()) => {}
+                Err(ActionError::Obstacle((x, y))) => {
+                    map.set_explored((x, y), true);
+                    base.edited(map, &[(x, y)]);
+                }
+                Err(ActionError::Occupied(cell)) => blocked.push((index, cell)),
+                Err(_) => {}
             }
         }
-        Task::CollectMinerals => {
-            if let Some(&(x, y)) = map
-                .minerals
-                .iter()
-                .find(|&&(mx, my)| mx == robot.x && my == robot.y)
-            {
-                robot.minerals += 1;
-                map.minerals.retain(|&pos| pos != (x, y));
-                robot.state = RobotState::Returning;
-            } else {
-                if let Some(target) = map
-                    .minerals
-                    .iter()
-                    .min_by_key(|&&(x, y)| {
-                        ((robot.x as isize - x as isize).abs() + (robot.y as isize - y as isize).abs())
-                            as isize
-                    })
-                    .cloned()
-                {
-                    robot.move_towards(target);
-                } else {
-                    robot.state = RobotState::Returning;
-                }
+        if blocked.is_empty() || blocked.len() == pending.len() {
+            for (index, cell) in blocked {
+                base.traffic.stats.waits += 1;
+                *base.traffic.stats.jams.entry(cell).or_insert(0) += 1;
+                robots[index].blocked += 1;
             }
+            break;
         }
-        _ => {}
+        pending = blocked.into_iter().map(|(index, _)| index).collect();
+    }
+
+    // Les robots qui n'avancent plus depuis longtemps sont signalés
+    if let Some(fleet) = base.traffic.fleet.as_mut() {
+        fleet.detect_deadlocks(robots, clock.tick);
     }
 }
 
-pub fn is_map_fully_explored(map: &Map) -> bool {
-    for row in &map.explored {
-        if row.contains(&false) {
-            return false;
+// Fonction pour faire équiper par la base un robot qui vient de s'y recharger
+fn service(map: &Map, robot: &mut Robot, base: &mut Base, action: Action) {
+    if action == Action::Recharge {
+        base.fit_best_upgrade(map, robot);
+    }
+}
+
+// Fonction pour faire choisir son action à un robot : il révèle les alentours, puis décide
+// sans viser les gisements revendiqués par les autres ; le gisement qu'il vise lui est réservé
+fn choose(
+    map: &mut Map,
+    robot: &mut Robot,
+    index: usize,
+    base: &mut Base,
+    clock: &Clock,
+    range: usize,
+) -> Action {
+    reveal_around(map, (robot.x, robot.y), range);
+    base.revealed(map, (robot.x, robot.y), range);
+    let view = RobotView::guided(robot, map, range, &base.home);
+    let mut knowledge = Knowledge::new(map, clock)
+        .excluding(base.claims.held_by_others(index))
+        .with_home(&base.home)
+        .with_frontier(&base.frontier);
+    if let Some(hierarchy) = &base.hierarchy {
+        knowledge = knowledge.with_hierarchy(hierarchy);
+    }
+    let action = behavior::decide(robot, &view, &knowledge);
+    base.claims.renew(map, robot, index, clock.tick);
+    action
+}
+
+// Fonction pour recharger un robot au soleil et obtenir la portée de son capteur pour ce tour
+fn sense(robot: &mut Robot, clock: &Clock) -> usize {
+    // Les panneaux solaires rechargent un peu la batterie en plein jour
+    robot.battery = (robot.battery + clock.solar_charge()).min(robot.modules.battery_capacity());
+    clock.sensor_range(robot.modules.sensor_range())
+}
+
+// Fonction pour faire avancer la simulation d'un tour
+pub fn simulate_tick(map: &mut Map, robots: &mut Vec<Robot>, base: &mut Base, clock: &mut Clock) {
+    regenerate_deposits(map, clock.is_day());
+
+    // Le champ de distances et l'index des frontières ne sont construits qu'au premier tour ou
+    // quand la base a bougé ; ils sont ensuite réparés à chaque découverte, et les cases que
+    // la roche isole de la base sont repérées au passage
+    base.prepare(map);
+    base.claims.expire(map, clock.tick);
+    base.traffic.begin_tick(map, robots);
+    if base.traffic.fleet.is_some() {
+        act_planned(map, robots, base, clock);
+    } else {
+        for (index, robot) in robots.iter_mut().enumerate() {
+            act(map, robot, index, base, clock);
         }
     }
-    true
+
+    // Le planificateur de la base réaffecte périodiquement les robots selon les besoins
+    scheduler::schedule(map, robots, base, clock);
+
+    // La base assemble un nouveau robot quand un besoin apparaît et que le stock le permet
+    if let Some((task, modules)) = base.plan_robot(map, robots) {
+        if let Some(robot) = base.assemble(map, task, modules) {
+            robots.push(robot);
+        }
+    }
+
+    clock.advance();
 }
 
-pub fn generate_map(width: usize, height: usize) -> Map {
-    let mut map = Map {
+// Fonction pour vérifier si la carte est entièrement explorée, les cases hors d'atteinte
+// exceptées, d'après l'index des frontières de la base
+pub fn is_map_fully_explored(map: &Map, base: &Base) -> bool {
+    base.frontier.count(map) == 0
+}
+
+// Fonction pour créer une carte vide, sans obstacle ni ressource
+pub fn empty_map(width: usize, height: usize) -> Map {
+    empty_map_with(width, height, ResourceRegistry::standard())
+}
+
+// Fonction pour créer une carte vide avec un registre de ressources donné
+pub fn empty_map_with(width: usize, height: usize, resources: ResourceRegistry) -> Map {
+    Map {
         width,
         height,
-        obstacles: vec![vec![false; width]; height],
-        energy: vec![],
-        minerals: vec![],
+        cells: vec![
+            Cell {
+                terrain: Terrain::Plain,
+                explored: false,
+                unreachable: false,
+                deposit: None,
+            };
+            width * height
+        ],
+        deposits: vec![Vec::new(); resources.len()],
+        deposit_slots: HashMap::new(),
+        deposit_index: vec![DepositIndex::new(width, height); resources.len()],
+        depleted: Vec::new(),
+        revision: 0,
+        resources,
         base: (width / 2, height / 2),
-        explored: vec![vec![false; width]; height],
-    };
+    }
+}
+
+// Fonction pour générer une carte aléatoire
+pub fn generate_map(width: usize, height: usize) -> Map {
+    generate_map_with(width, height, ResourceRegistry::standard())
+}
 
-    let mut rng = rand::thread_rng();
+// Fonction pour générer une carte aléatoire avec un registre de ressources donné
+pub fn generate_map_with(width: usize, height: usize, resources: ResourceRegistry) -> Map {
     let seed = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap()
         .as_secs();
+    generate_map_seeded(width, height, resources, seed)
+}
+
+// Fonction pour générer une carte à partir d'une graine : une même graine donne la même carte,
+// ce qui permet de comparer des politiques sur des parties identiques
+pub fn generate_map_seeded(
+    width: usize,
+    height: usize,
+    resources: ResourceRegistry,
+    seed: u64,
+) -> Map {
+    let mut map = empty_map_with(width, height, resources);
 
+    let mut rng = StdRng::seed_from_u64(seed);
     let perlin = Perlin::new(seed as u32);
 
     for y in 0..height {
         for x in 0..width {
             let value = perlin.get([x as f64 / 10.0, y as f64 / 10.0, 0.0]);
-            if value > 0.5 {
-                map.obstacles[y][x] = true;
-            }
-        }
-    }
-
-    for _ in 0..10 {
-        let mut x;
-        let mut y;
-        loop {
-            x = rng.gen_range(0..width);
-            y = rng.gen_range(0..height);
-            if (x, y) != map.base {
-                break;
-            }
+            map.set_terrain((x, y), Terrain::from_noise(value));
         }
-        map.energy.push((x, y));
     }
+    map.set_terrain(map.base, Terrain::Plain);
 
-    for _ in 0..10 {
-        let mut x;
-        let mut y;
-        loop {
-            x = rng.gen_range(0..width);
-            y = rng.gen_range(0..height);
-            if (x, y) != map.base {
+    // Chaque type de ressource reçoit un gisement pour `rarity` cases libres, à distance de la base
+    let free = map
+        .cells()
+        .iter()
+        .filter(|cell| cell.terrain != Terrain::Rock)
+        .count();
+    for kind in map.resources.kinds().collect::<Vec<_>>() {
+        let def = map.resources.get(kind);
+        let count = (free / def.rarity).max(1);
+        let min_distance = def.min_distance;
+        let mut candidates: Vec<(usize, usize)> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter(|&(x, y)| {
+                (x, y) != map.base
+                    && !map.is_obstacle((x, y))
+                    && map.deposit_at((x, y)).is_none()
+                    && x.abs_diff(map.base.0) + y.abs_diff(map.base.1) >= min_distance
+            })
+            .collect();
+        for _ in 0..count {
+            if candidates.is_empty() {
                 break;
             }
+            let cell = candidates.swap_remove(rng.gen_range(0..candidates.len()));
+            map.add_deposit(kind, cell);
         }
-        map.minerals.push((x, y));
     }
 
     map
 }
-*/
@@ -1,340 +1,437 @@
-use minifb::{Window, WindowOptions};
-use noise::{NoiseFn, Perlin};
-use rand::Rng;
-use std::time::{SystemTime, UNIX_EPOCH};
-use std::{thread, time};
-
-// Structure représentant la carte
-struct Map {
-    width: usize,                  // largeur de la carte
-    height: usize,                 // hauteur de la carte
-    obstacles: Vec<Vec<bool>>,     // true si obstacle, false sinon
-    energy: Vec<(usize, usize)>,   // positions des sources d'énergie
-    minerals: Vec<(usize, usize)>, // positions des gisements de minerais
-    base: (usize, usize),          // position de la base
-    explored: Vec<Vec<bool>>,      // true si la case a été explorée, false sinon
-}
+use erea::base::Base;
+use erea::clock::{Clock, DAY_LENGTH};
+use erea::editor::{erase, paint, reveal, Tool};
+use erea::export::{save_frame, Frame, GifRecorder};
+use erea::hpa::{Hierarchy, HIERARCHY_MIN_SIZE};
+use erea::map_file::{load_map_with, save_map};
+use erea::mapf::PLAN_WINDOW;
+use erea::modules::Modules;
+use erea::pathfinding::ReplanStats;
+use erea::resources::{
+    load_registry, ResourceKind, ResourceRegistry, ENERGY_KEY, MINERAL_KEY, SCIENCE_KEY,
+};
+use erea::scheduler::{policy_from_name, Scheduler, SCHEDULER_PERIOD};
+use erea::script::ScriptBehavior;
+use erea::traffic::Traffic;
+use erea::ui::{cell_at, draw_ui, highlight, inspect_cell, inspect_robot};
+use erea::{
+    generate_map_seeded, generate_map_with, present, render_map, render_scene, simulate_tick, Map,
+    Robot, Task,
+};
+use minifb::{Key, KeyRepeat, MouseButton, MouseMode, Window, WindowOptions};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::{env, thread, time};
 
-// Structure représentant un robot
-struct Robot {
-    x: usize,                      // position x
-    y: usize,                      // position y
-    energy: usize,                 // quantité d'énergie collectée
-    minerals: usize,               // quantité de minerais collectée
-    task: Task,                    // tâche actuelle
-    state: RobotState,             // état actuel
-}
+// Nombre de tours simulés sans affichage lorsque la fenêtre ne peut pas s'ouvrir
+const DEFAULT_HEADLESS_TICKS: usize = 500;
+
+// Taille en pixels d'une case dans la fenêtre
+const CELL_PIXELS: usize = 20;
+
+// Position du panneau de l'inspecteur dans la fenêtre, en pixels
+const PANEL_ANCHOR: (usize, usize) = (4, 4);
+
+// Rayon autour de la base dans lequel les attentes comptent comme un embouteillage de la base
+const JAM_RADIUS: usize = 2;
 
-// Enumération des tâches possibles pour un robot
-#[derive(PartialEq)]
-enum Task {
-    CollectEnergy,
-    CollectMinerals,
-    Explore,
+// Elément sélectionné à la souris dans la fenêtre
+enum Selection {
+    Cell((usize, usize)),
+    Robot(usize),
 }
 
-// Enumération des états possibles pour un robot
-enum RobotState {
-    Exploring,
-    Returning,
-    Collecting,
+// Export des images de la simulation : fichiers PNG réguliers et GIF animé
+struct Exporter {
+    every: Option<usize>,               // enregistre une image tous les N tours
+    directory: PathBuf,                 // dossier des images enregistrées
+    scale: usize,                       // taille en pixels d'une case dans les images
+    gif: Option<(String, GifRecorder)>, // fichier et enregistreur du GIF animé
 }
 
-impl Robot {
-    // Fonction pour créer un nouveau robot
-    fn new(x: usize, y: usize, task: Task) -> Self {
-        Robot {
-            x,
-            y,
-            energy: 0,
-            minerals: 0,
-            task,
-            state: RobotState::Exploring,
+impl Exporter {
+    fn from_args(args: &[String]) -> Self {
+        Exporter {
+            every: option_value(args, "--export-every").map(parse_number),
+            directory: PathBuf::from(option_value(args, "--export-dir").unwrap_or("frames")),
+            scale: option_value(args, "--scale").map_or(10, parse_number),
+            gif: option_value(args, "--gif").map(|path| (path.to_string(), GifRecorder::new(10))),
         }
     }
 
-    // Fonction pour déplacer le robot vers une position cible
-    fn move_towards(&mut self, target: (usize, usize)) {
-        let dx = if self.x < target.0 {
-            1
-        } else if self.x > target.0 {
-            -1
-        } else {
-            0
-        };
-        let dy = if self.y < target.1 {
-            1
-        } else if self.y > target.1 {
-            -1
-        } else {
-            0
-        };
-        let new_x = (self.x as isize).wrapping_add(dx as isize) as usize;
-        let new_y = (self.y as isize).wrapping_add(dy as isize) as usize;
-        self.x = new_x;
-        self.y = new_y;
+    // Fonction appelée à chaque tour avec l'image courante
+    fn record(&mut self, tick: usize, map: &Map, buffer: &[u32]) {
+        // Sans --export-every, seul le GIF est alimenté, à chaque tour
+        let due = self
+            .every
+            .is_none_or(|every| tick.is_multiple_of(every.max(1)));
+        if !due {
+            return;
+        }
+        if self.every.is_some() {
+            self.snapshot(tick, map, buffer);
+        }
+        if let Some((_, recorder)) = &mut self.gif {
+            recorder.add_frame(Frame::new(map.width, map.height, buffer.to_vec()));
+        }
     }
-}
 
-// Fonction pour dessiner la carte et les robots
-fn draw_map(window: &mut Window, map: &Map, robots: &[Robot]) {
-    let mut buffer: Vec<u32> = vec![0; map.width * map.height];
-    for y in 0..map.height {
-        for x in 0..map.width {
-            let index = y * map.width + x;
-            if (x == map.base.0
-                && y >= map.base.1.saturating_sub(1)
-                && y <= map.base.1.saturating_add(1))
-                || (y == map.base.1
-                    && x >= map.base.0.saturating_sub(1)
-                    && x <= map.base.0.saturating_add(1))
-            {
-                buffer[index] = 0xFF_00FFFF;
-            } else if map.explored[y][x] {
-                if map.obstacles[y][x] {
-                    buffer[index] = 0xFF_000000;
-                } else if map.energy.contains(&(x, y)) || map.minerals.contains(&(x, y)) {
-                    if !map.explored[y][x] {
-                        buffer[index] = 0xFF_AAAAAA;
-                    } else {
-                        buffer[index] = if map.energy.contains(&(x, y)) {
-                            0xFF_00FF00
-                        } else {
-                            0xFFFF0000
-                        };
-                    }
-                } else {
-                    buffer[index] = 0xFF_FFFFFF;
-                }
-            } else {
-                buffer[index] = 0xFF_AAAAAA;
-            }
+    // Fonction pour enregistrer l'image courante en PNG
+    fn snapshot(&self, tick: usize, map: &Map, buffer: &[u32]) {
+        let frame = Frame::new(map.width, map.height, buffer.to_vec()).scaled(self.scale);
+        let path = self.directory.join(format!("frame_{:05}.png", tick));
+        let result = fs::create_dir_all(&self.directory).and_then(|_| save_frame(&frame, &path));
+        match result {
+            Ok(()) => println!("Image enregistrée dans {}", path.display()),
+            Err(e) => eprintln!("Impossible d'enregistrer {}: {}", path.display(), e),
         }
     }
 
-    for robot in robots {
-        let index = robot.y * map.width + robot.x;
-        let color = match robot.state {
-            RobotState::Exploring => 0xFF_FFFF00, // Jaune pour l'exploration
-            RobotState::Collecting => 0xFFFF00FF, // Rose pour la collecte
-            RobotState::Returning => 0xFFFF00FF,  // Rose pour le retour à la base
-        };
-        buffer[index] = color;
+    // Fonction pour écrire le GIF animé à la fin de la simulation
+    fn finish(&self) {
+        if let Some((path, recorder)) = &self.gif {
+            match recorder.save(path, self.scale) {
+                Ok(()) => println!(
+                    "Animation de {} images enregistrée dans {}",
+                    recorder.len(),
+                    path
+                ),
+                Err(e) => eprintln!("Impossible d'enregistrer {}: {}", path, e),
+            }
+        }
     }
-
-    window
-        .update_with_buffer(&buffer, map.width, map.height)
-        .unwrap();
 }
 
-// Fonction pour explorer la carte
-fn explore_map(robot: &mut Robot, map: &mut Map) {
-    let mut target = (robot.x, robot.y);
-    let mut min_distance = isize::MAX;
-
-    for y in 0..map.height {
-        for x in 0..map.width {
-            if !map.explored[y][x] {
-                let distance = ((robot.x as isize - x as isize).abs()
-                    + (robot.y as isize - y as isize).abs())
-                    as isize;
-                if distance < min_distance {
-                    min_distance = distance;
-                    target = (x, y);
-                }
+fn main() {
+    // Options : --map <fichier> pour charger une carte, --editor <fichier> pour l'éditer,
+    // --headless <tours> pour simuler sans fenêtre, --export-every <tours>, --export-dir <dossier>,
+    // --gif <fichier> et --scale <pixels> pour exporter des images, --resources <fichier> pour
+    // remplacer le registre de ressources standard, --day-length <tours> pour la durée d'un jour,
+    // --policy <fixed|balanced|auction> et --schedule-every <tours> pour le planificateur de la base,
+    // --script <fichier> pour piloter les robots par un script Rhai rechargé à chaque modification,
+    // --exclusive-cells pour n'admettre qu'un robot par case hors de la base, --fleet-planning et
+    // --plan-window <tours> pour planifier en commun les déplacements de la flotte, --seed <graine>
+    // pour générer toujours la même carte, --size <cases> pour le côté de la carte générée et
+    // --hierarchical pour planifier les trajets par la hiérarchie de groupes, automatique sur les
+    // grandes cartes
+    let args: Vec<String> = env::args().collect();
+    let map_path = option_value(&args, "--map");
+    let editor_path = option_value(&args, "--editor");
+    let resources = match option_value(&args, "--resources") {
+        Some(path) => load_registry(path).unwrap_or_else(|e| panic!("{}: {}", path, e)),
+        None => ResourceRegistry::standard(),
+    };
+
+    let mut map = match map_path.or(editor_path) {
+        Some(path) if Path::new(path).exists() => {
+            load_map_with(path, &resources).unwrap_or_else(|e| panic!("{}: {}", path, e))
+        }
+        _ => {
+            let size = option_value(&args, "--size").map_or(35, parse_number);
+            match option_value(&args, "--seed") {
+                Some(seed) => generate_map_seeded(size, size, resources, parse_number(seed) as u64),
+                None => generate_map_with(size, size, resources),
             }
         }
-    }
+    };
 
-    for dy in -1..=1 {
-        for dx in -1..=1 {
-            let x = (robot.x as isize + dx) as usize;
-            let y = (robot.y as isize + dy) as usize;
-            if x < map.width && y < map.height {
-                map.explored[y][x] = true;
+    let window = if option_value(&args, "--headless").is_some() {
+        None
+    } else {
+        match Window::new(
+            "Rust Game",
+            map.width * CELL_PIXELS,
+            map.height * CELL_PIXELS,
+            WindowOptions::default(),
+        ) {
+            Ok(window) => Some(window),
+            Err(e) if editor_path.is_none() => {
+                println!("Fenêtre indisponible ({}), simulation sans affichage", e);
+                None
             }
+            Err(e) => panic!("{}", e),
         }
-    }
+    };
 
-    robot.move_towards(target);
-}
+    if let Some(path) = editor_path {
+        let mut window = window.expect("le mode éditeur nécessite une fenêtre");
+        run_editor(&mut window, &mut map, path);
+        return;
+    }
 
-// Fonction pour collecter des ressources
-fn collect_resources(robot: &mut Robot, map: &mut Map) {
-    match robot.task {
-        Task::CollectEnergy => {
-            if let Some(target) = map
-                .energy
-                .iter()
-                .min_by_key(|&&(x, y)| {
-                    ((robot.x as isize - x as isize).abs() + (robot.y as isize - y as isize).abs())
-                        as isize
-                })
-                .cloned()
-            {
-                if (robot.x, robot.y) == target {
-                    robot.energy += 1;
-                    map.energy.retain(|&pos| pos != target);
-                    robot.state = RobotState::Returning;
-                } else {
-                    robot.move_towards(target);
-                }
-            } else {
-                robot.state = RobotState::Returning;
+    // Flotte de départ : un éclaireur, puis un collecteur pour l'énergie, les minerais et la
+    // science, retrouvées par leur mot-clé dans le registre et équipées selon leur extraction
+    let (x, y) = map.base;
+    let mut robots = vec![Robot::with_modules(x, y, Task::Explore, Modules::scout())];
+    for key in [ENERGY_KEY, MINERAL_KEY, SCIENCE_KEY] {
+        match map.resources.by_key(key) {
+            Some(kind) => {
+                let modules = Modules::collector(map.resources.get(kind).extraction);
+                robots.push(Robot::with_modules(x, y, Task::Collect(kind), modules));
             }
+            None => println!("Ressource '{}' absente du registre : aucun collecteur", key),
         }
-        Task::CollectMinerals => {
-            if let Some(target) = map
-                .minerals
-                .iter()
-                .min_by_key(|&&(x, y)| {
-                    ((robot.x as isize - x as isize).abs() + (robot.y as isize - y as isize).abs())
-                        as isize
-                })
-                .cloned()
-            {
-                if (robot.x, robot.y) == target {
-                    robot.minerals += 1;
-                    map.minerals.retain(|&pos| pos != target);
-                    robot.state = RobotState::Returning;
-                } else {
-                    robot.move_towards(target);
-                }
-            } else {
-                robot.state = RobotState::Returning;
-            }
+    }
+    let mut base = Base::new();
+    let policy = option_value(&args, "--policy").unwrap_or("balanced");
+    base.scheduler = Scheduler::new(
+        policy_from_name(policy).unwrap_or_else(|| panic!("politique inconnue : {}", policy)),
+        option_value(&args, "--schedule-every").map_or(SCHEDULER_PERIOD, parse_number),
+    );
+    base.traffic = if args.iter().any(|arg| arg == "--fleet-planning") {
+        Traffic::planned(option_value(&args, "--plan-window").map_or(PLAN_WINDOW, parse_number))
+    } else {
+        Traffic::new(args.iter().any(|arg| arg == "--exclusive-cells"))
+    };
+    if args.iter().any(|arg| arg == "--hierarchical")
+        || map.width.max(map.height) >= HIERARCHY_MIN_SIZE
+    {
+        base.hierarchy = Some(Hierarchy::new(&map));
+    }
+    let mut clock =
+        Clock::new(option_value(&args, "--day-length").map_or(DAY_LENGTH, parse_number));
+
+    let script = option_value(&args, "--script");
+    if let Some(path) = script {
+        if let Err(e) = ScriptBehavior::load(path) {
+            panic!("{}: {}", path, e);
         }
-        _ => {}
     }
-}
 
-// Fonction pour vérifier si la carte est entièrement explorée
-fn is_map_fully_explored(map: &Map) -> bool {
-    for row in &map.explored {
-        if row.contains(&false) {
-            return false;
+    let mut exporter = Exporter::from_args(&args);
+    match window {
+        Some(mut window) => run_window(
+            &mut window,
+            &mut map,
+            &mut robots,
+            &mut base,
+            &mut clock,
+            &mut exporter,
+            script,
+        ),
+        None => {
+            let ticks =
+                option_value(&args, "--headless").map_or(DEFAULT_HEADLESS_TICKS, parse_number);
+            for tick in 1..=ticks {
+                attach_script(&mut robots, script);
+                simulate_tick(&mut map, &mut robots, &mut base, &mut clock);
+                exporter.record(tick, &map, &render_scene(&map, &robots, &clock));
+            }
+            let stock: Vec<String> = map
+                .resources
+                .kinds()
+                .map(|kind| format!("{} {}", base.stock(kind), map.resources.get(kind).name))
+                .collect();
+            println!(
+                "{} tours simulés (jour {}), {} robots, politique {}, stock : {}",
+                ticks,
+                clock.day(),
+                robots.len(),
+                base.scheduler.policy.name(),
+                stock.join(", ")
+            );
+            println!(
+                "Collecte : {} voyages, {:.1} unités par voyage, {:.1} de batterie par unité",
+                base.trips.trips,
+                base.trips.units_per_trip(),
+                base.trips.spent_per_unit()
+            );
+            let mut replans = ReplanStats::default();
+            for robot in &robots {
+                replans.add(robot.route.stats);
+            }
+            println!(
+                "Replanification : {} recherches, {} réparations, {} cases développées",
+                replans.searches, replans.repairs, replans.expansions
+            );
+            if base.traffic.exclusive {
+                let stats = &base.traffic.stats;
+                println!(
+                    "Circulation : {} attentes dont {} près de la base, {} écarts",
+                    stats.waits,
+                    stats.jams_near(map.base, JAM_RADIUS),
+                    stats.yields
+                );
+            }
+            if let Some(fleet) = &base.traffic.fleet {
+                for deadlock in &fleet.deadlocks {
+                    println!(
+                        "Interblocage au tour {} : robots {:?} en {:?}",
+                        deadlock.tick, deadlock.robots, deadlock.cells
+                    );
+                }
+                println!(
+                    "Planification : {} interblocages, {} reculs, {} recherches sans solution, {} champs de distances calculés",
+                    fleet.deadlocks.len(),
+                    fleet.backoffs,
+                    fleet.unresolved,
+                    fleet.heuristics.builds
+                );
+            }
         }
     }
-    true
+    exporter.finish();
 }
 
-fn main() {
-    let width = 35;
-    let height = 35;
-
-    let mut window = Window::new(
-        "Rust Game",
-        width * 20,
-        height * 20,
-        WindowOptions::default(),
-    )
-    .unwrap_or_else(|e| {
-        panic!("{}", e);
-    });
-
-    let mut map = generate_map(width, height);
-
-    let mut robots = Vec::new();
-    let x = map.base.0;
-    let y = map.base.1;
-    robots.push(Robot::new(x, y, Task::Explore));
-    robots.push(Robot::new(x, y, Task::CollectEnergy));
-    robots.push(Robot::new(x, y, Task::CollectMinerals));
+// Boucle principale avec fenêtre : clic gauche pour inspecter, P pour enregistrer l'image courante
+fn run_window(
+    window: &mut Window,
+    map: &mut Map,
+    robots: &mut Vec<Robot>,
+    base: &mut Base,
+    clock: &mut Clock,
+    exporter: &mut Exporter,
+    script: Option<&str>,
+) {
+    let mut selection: Option<Selection> = None;
+    let mut mouse_was_down = false;
+    let mut tick = 0;
 
     while window.is_open() {
-        let mut explorer_returned = false;
-
-        for robot in &mut robots {
-            match robot.state {
-                RobotState::Exploring => {
-                    if is_map_fully_explored(&map) {
-                        robot.state = RobotState::Returning;
-                    } else {
-                        explore_map(robot, &mut map);
-                    }
-                }
-                RobotState::Returning => {
-                    robot.move_towards(map.base);
-                    if (robot.x, robot.y) == map.base {
-                        if robot.task == Task::Explore {
-                            explorer_returned = true;
-                        }
-                        robot.state = RobotState::Collecting;
-                    }
-                }
-                RobotState::Collecting => {
-                    collect_resources(robot, &mut map);
-                }
+        tick += 1;
+        attach_script(robots, script);
+        simulate_tick(map, robots, base, clock);
+
+        // Sélection d'une case ou d'un robot au clic gauche
+        let mouse_down = window.get_mouse_down(MouseButton::Left);
+        if mouse_down && !mouse_was_down {
+            if let Some(cell) = window
+                .get_mouse_pos(MouseMode::Discard)
+                .and_then(|mouse| cell_at(map, window.get_size(), mouse))
+            {
+                selection = match robots.iter().position(|r| (r.x, r.y) == cell) {
+                    Some(index) => Some(Selection::Robot(index)),
+                    None => Some(Selection::Cell(cell)),
+                };
             }
         }
+        mouse_was_down = mouse_down;
 
-        if explorer_returned {
-            for robot in &mut robots {
-                if robot.task != Task::Explore {
-                    robot.state = RobotState::Collecting;
-                }
-            }
+        let frame = render_scene(map, robots, clock);
+        exporter.record(tick, map, &frame);
+        if window.is_key_pressed(Key::P, KeyRepeat::No) {
+            exporter.snapshot(tick, map, &frame);
         }
 
-        draw_map(&mut window, &map, &robots);
+        let mut buffer = frame;
+        let elements = match selection {
+            Some(Selection::Cell(cell)) => {
+                highlight(&mut buffer, map, &[cell], 0xFF_0000FF);
+                inspect_cell(map, robots, cell, PANEL_ANCHOR)
+            }
+            Some(Selection::Robot(index)) => {
+                let robot = &robots[index];
+                highlight(&mut buffer, map, &robot.planned_path(), 0xFF_0000FF);
+                inspect_robot(map, robot, index, PANEL_ANCHOR)
+            }
+            None => Vec::new(),
+        };
+
+        // Le panneau de l'inspecteur est dessiné par-dessus la carte agrandie
+        let mut view = Frame::new(map.width, map.height, buffer).scaled(CELL_PIXELS);
+        draw_ui(&mut view.pixels, view.width, &elements);
+        window
+            .update_with_buffer(&view.pixels, view.width, view.height)
+            .unwrap();
         thread::sleep(time::Duration::from_millis(10));
     }
 }
 
-// Fonction pour générer une carte aléatoire
-fn generate_map(width: usize, height: usize) -> Map {
-    let mut map = Map {
-        width,
-        height,
-        obstacles: vec![vec![false; width]; height],
-        energy: vec![],
-        minerals: vec![],
-        base: (width / 2, height / 2),
-        explored: vec![vec![false; width]; height],
+// Fonction pour lire un nombre passé en option
+fn parse_number(value: &str) -> usize {
+    value
+        .parse()
+        .unwrap_or_else(|_| panic!("nombre attendu : {}", value))
+}
+
+// Fonction pour confier le script aux robots qui n'ont pas encore de stratégie, y compris ceux
+// que la base vient d'assembler. Tant que le fichier est invalide, ils suivent leur tâche.
+fn attach_script(robots: &mut [Robot], script: Option<&str>) {
+    let Some(path) = script else {
+        return;
     };
+    for robot in robots.iter_mut().filter(|robot| robot.behavior.is_none()) {
+        if let Ok(behavior) = ScriptBehavior::load(path) {
+            robot.behavior = Some(Box::new(behavior));
+        }
+    }
+}
 
-    let mut rng = rand::thread_rng();
-    let seed = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
+// Fonction pour lire la valeur d'une option de la ligne de commande
+fn option_value<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == name)
+        .and_then(|index| args.get(index + 1))
+        .map(|value| value.as_str())
+}
 
-    let perlin = Perlin::new(seed as u32);
+// Mode éditeur : clic gauche pour poser, clic droit pour effacer,
+// 1 à 9 pour choisir l'outil et S pour enregistrer la carte
+fn run_editor(window: &mut Window, map: &mut Map, path: &str) {
+    let mut tool = Tool::Obstacle;
+    let mut title = String::new();
+    let deposit_keys = deposit_keys(&map.resources);
+    reveal(map);
 
-    for y in 0..height {
-        for x in 0..width {
-            let value = perlin.get([x as f64 / 10.0, y as f64 / 10.0, 0.0]);
-            if value > 0.5 {
-                map.obstacles[y][x] = true;
+    while window.is_open() && !window.is_key_down(Key::Escape) {
+        for key in window.get_keys_pressed(KeyRepeat::No) {
+            match key {
+                Key::Key1 => tool = Tool::Obstacle,
+                Key::Key4 => tool = Tool::Base,
+                Key::Key5 => tool = Tool::Sand,
+                Key::Key6 => tool = Tool::Rough,
+                Key::S => match save_map(map, path) {
+                    Ok(()) => println!("Carte enregistrée dans {}", path),
+                    Err(e) => eprintln!("Impossible d'enregistrer {}: {}", path, e),
+                },
+                other => {
+                    if let Some(&(_, kind)) = deposit_keys.iter().find(|(key, _)| *key == other) {
+                        tool = Tool::Deposit(kind);
+                    }
+                }
             }
         }
-    }
 
-    for _ in 0..10 {
-        let mut x;
-        let mut y;
-        loop {
-            x = rng.gen_range(0..width);
-            y = rng.gen_range(0..height);
-            if (x, y) != map.base {
-                break;
+        if let Some(cell) = window
+            .get_mouse_pos(MouseMode::Discard)
+            .and_then(|mouse| cell_at(map, window.get_size(), mouse))
+        {
+            if window.get_mouse_down(MouseButton::Left) {
+                paint(map, tool, cell);
+            } else if window.get_mouse_down(MouseButton::Right) {
+                erase(map, tool, cell);
             }
         }
-        map.energy.push((x, y));
-    }
 
-    for _ in 0..10 {
-        let mut x;
-        let mut y;
-        loop {
-            x = rng.gen_range(0..width);
-            y = rng.gen_range(0..height);
-            if (x, y) != map.base {
-                break;
-            }
+        let current = format!("Editeur - outil : {:?} - {}", tool, path);
+        if current != title {
+            window.set_title(&current);
+            title = current;
         }
-        map.minerals.push((x, y));
+
+        present(window, map, &render_map(map, &[]));
+        thread::sleep(time::Duration::from_millis(10));
     }
+}
 
-    map
+// Fonction pour associer les touches de l'éditeur aux gisements : 2, 3 et 7 pour l'énergie,
+// les minerais et la science, retrouvés par leur mot-clé, puis 8 et 9 pour les autres types
+// du registre, dans son ordre
+fn deposit_keys(resources: &ResourceRegistry) -> Vec<(Key, ResourceKind)> {
+    let standard = [
+        (Key::Key2, ENERGY_KEY),
+        (Key::Key3, MINERAL_KEY),
+        (Key::Key7, SCIENCE_KEY),
+    ];
+    let mut keys: Vec<(Key, ResourceKind)> = standard
+        .iter()
+        .filter_map(|&(key, name)| Some((key, resources.by_key(name)?)))
+        .collect();
+    let others = resources.kinds().filter(|&kind| {
+        !standard
+            .iter()
+            .any(|&(_, name)| resources.get(kind).key == name)
+    });
+    keys.extend([Key::Key8, Key::Key9].into_iter().zip(others));
+    keys
 }
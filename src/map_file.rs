@@ -0,0 +1,153 @@
+// src/map_file.rs
+use crate::ascii_map::{map_to_ascii, parse_ascii_map_with};
+use crate::resources::ResourceRegistry;
+use crate::terrain::Terrain;
+use crate::{empty_map_with, Map};
+use std::fs;
+use std::io::{self, Error, ErrorKind};
+use std::path::Path;
+
+// Format texte d'une carte, une instruction par ligne :
+//
+//   size <largeur> <hauteur>
+//   base <x> <y>
+//   obstacle <x> <y>
+//   sand <x> <y>
+//   rough <x> <y>
+//   <ressource> <x> <y>
+//
+// où <ressource> est le mot-clé d'un type du registre (energy, mineral, science pour le
+// registre standard). Les lignes vides et celles commençant par '#' sont ignorées.
+// Les fichiers d'extension .txt utilisent le format ASCII (voir ascii_map).
+
+// Fonction pour convertir une carte en texte
+pub fn map_to_string(map: &Map) -> String {
+    let mut text = String::from("# erea map\n");
+    text.push_str(&format!("size {} {}\n", map.width, map.height));
+    text.push_str(&format!("base {} {}\n", map.base.0, map.base.1));
+    for y in 0..map.height {
+        for x in 0..map.width {
+            if map.is_obstacle((x, y)) {
+                text.push_str(&format!("obstacle {} {}\n", x, y));
+            } else if map.terrain_at((x, y)) == Terrain::Sand {
+                text.push_str(&format!("sand {} {}\n", x, y));
+            } else if map.terrain_at((x, y)) == Terrain::Rough {
+                text.push_str(&format!("rough {} {}\n", x, y));
+            }
+        }
+    }
+    for kind in map.resources.kinds() {
+        for (x, y) in map.deposits(kind) {
+            text.push_str(&format!("{} {} {}\n", map.resources.get(kind).key, x, y));
+        }
+    }
+    text
+}
+
+// Fonction pour lire une carte depuis du texte
+pub fn map_from_str(text: &str) -> io::Result<Map> {
+    map_from_str_with(text, &ResourceRegistry::standard())
+}
+
+// Fonction pour lire une carte depuis du texte avec un registre de ressources donné
+pub fn map_from_str_with(text: &str, resources: &ResourceRegistry) -> io::Result<Map> {
+    let mut map: Option<Map> = None;
+
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let values = parse_pair(&words, number + 1)?;
+
+        if words[0] == "size" {
+            if map.is_some() {
+                return Err(invalid(number + 1, "taille déjà définie"));
+            }
+            map = Some(empty_map_with(values.0, values.1, resources.clone()));
+            continue;
+        }
+
+        let map = match map.as_mut() {
+            Some(map) => map,
+            None => {
+                return Err(invalid(
+                    number + 1,
+                    "la taille doit être définie en premier",
+                ))
+            }
+        };
+        if values.0 >= map.width || values.1 >= map.height {
+            return Err(invalid(number + 1, "position hors de la carte"));
+        }
+
+        match words[0] {
+            "base" => map.base = values,
+            "obstacle" => map.set_terrain(values, Terrain::Rock),
+            "sand" => map.set_terrain(values, Terrain::Sand),
+            "rough" => map.set_terrain(values, Terrain::Rough),
+            other => match resources.by_key(other) {
+                Some(kind) => map.add_deposit(kind, values),
+                None => {
+                    return Err(invalid(
+                        number + 1,
+                        &format!("instruction inconnue '{}'", other),
+                    ))
+                }
+            },
+        }
+    }
+
+    map.ok_or_else(|| Error::new(ErrorKind::InvalidData, "carte vide"))
+}
+
+// Fonction pour enregistrer une carte dans un fichier
+pub fn save_map(map: &Map, path: impl AsRef<Path>) -> io::Result<()> {
+    if is_ascii_file(path.as_ref()) {
+        fs::write(path, map_to_ascii(map))
+    } else {
+        fs::write(path, map_to_string(map))
+    }
+}
+
+// Fonction pour charger une carte depuis un fichier
+pub fn load_map(path: impl AsRef<Path>) -> io::Result<Map> {
+    load_map_with(path, &ResourceRegistry::standard())
+}
+
+// Fonction pour charger une carte depuis un fichier avec un registre de ressources donné
+pub fn load_map_with(path: impl AsRef<Path>, resources: &ResourceRegistry) -> io::Result<Map> {
+    let text = fs::read_to_string(path.as_ref())?;
+    if is_ascii_file(path.as_ref()) {
+        parse_ascii_map_with(&text, resources)
+    } else {
+        map_from_str_with(&text, resources)
+    }
+}
+
+fn is_ascii_file(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == "txt")
+}
+
+// Fonction pour lire les deux nombres qui suivent une instruction
+fn parse_pair(words: &[&str], line: usize) -> io::Result<(usize, usize)> {
+    if words.len() != 3 {
+        return Err(invalid(line, "deux valeurs attendues"));
+    }
+    let first = words[1]
+        .parse()
+        .map_err(|_| invalid(line, "nombre invalide"))?;
+    let second = words[2]
+        .parse()
+        .map_err(|_| invalid(line, "nombre invalide"))?;
+    Ok((first, second))
+}
+
+fn invalid(line: usize, message: &str) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!("ligne {} : {}", line, message),
+    )
+}
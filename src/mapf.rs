@@ -0,0 +1,454 @@
+// src/mapf.rs
+use crate::action::{Action, Direction};
+use crate::distance_field::DistanceField;
+use crate::pathfinding::neighbors;
+use crate::{Map, Robot, RobotState};
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+// Nombre de tours planifiés à l'avance pour toute la flotte
+pub const PLAN_WINDOW: usize = 16;
+
+// Nombre de tours d'attente forcée au bout duquel un robot est déclaré en interblocage
+pub const DEADLOCK_AFTER: usize = 10;
+
+// Nombre maximal d'états explorés par recherche de chemin ; au-delà, le robot s'approche au
+// mieux de sa cible
+const MAX_EXPANSIONS: usize = 4_000;
+
+// Etat d'une recherche espace-temps : une case à un tour donné
+type State = ((usize, usize), usize);
+
+// Table de réservation espace-temps : les cases occupées à chaque tour de la fenêtre et les
+// déplacements effectués entre deux tours. La base accueille plusieurs robots à la fois.
+#[derive(Debug, Default)]
+pub struct Reservations {
+    base: (usize, usize),
+    cells: HashSet<State>,
+    moves: HashSet<(State, (usize, usize))>,
+}
+
+impl Reservations {
+    // Fonction pour créer une table vide
+    pub fn new(base: (usize, usize)) -> Self {
+        Reservations {
+            base,
+            ..Reservations::default()
+        }
+    }
+
+    // Fonction pour savoir si une case est libre à un tour donné
+    pub fn is_free(&self, cell: (usize, usize), t: usize) -> bool {
+        cell == self.base || !self.cells.contains(&(cell, t))
+    }
+
+    // Fonction pour savoir si un robot peut passer d'une case à une autre entre les tours t et
+    // t + 1, sans croiser un robot qui ferait le trajet inverse
+    pub fn can_move(&self, from: (usize, usize), to: (usize, usize), t: usize) -> bool {
+        self.is_free(to, t + 1)
+            && (from == self.base || to == self.base || !self.moves.contains(&((to, t), from)))
+    }
+
+    // Fonction pour réserver un chemin, donné case par case à partir du tour 0
+    pub fn reserve(&mut self, path: &[(usize, usize)]) {
+        for (t, &cell) in path.iter().enumerate() {
+            self.cells.insert((cell, t));
+            if let Some(&next) = path.get(t + 1) {
+                self.moves.insert(((cell, t), next));
+            }
+        }
+    }
+}
+
+// Champ de distances vers une cible, avec les cases modifiées depuis sa dernière réparation
+// et le tour de sa dernière utilisation
+#[derive(Debug)]
+struct Field {
+    distances: DistanceField,
+    changed: Vec<(usize, usize)>,
+    used: usize,
+}
+
+// Champs de distances vers les cibles de la flotte, selon ce que les robots savent de la
+// carte, gardés d'un tour à l'autre pour guider les recherches de chemin. Les cases signalées
+// par la base (découvertes, obstacles heurtés, éditeur) sont notées, et un champ n'est réparé
+// à partir d'elles que lorsqu'une recherche s'en sert. Un champ est oublié dès qu'aucun robot
+// n'a visé sa cible pendant un tour.
+#[derive(Debug, Default)]
+pub struct Heuristics {
+    fields: HashMap<(usize, usize), Field>,
+    tick: usize,
+    pub builds: usize, // champs calculés entièrement
+}
+
+impl Heuristics {
+    // Fonction pour passer au tour suivant en oubliant les champs inutilisés
+    pub fn next_tick(&mut self) {
+        let tick = self.tick;
+        self.fields.retain(|_, field| field.used == tick);
+        self.tick += 1;
+    }
+
+    // Fonction pour obtenir le champ de distances vers une cible, calculé au besoin
+    pub fn field(&mut self, map: &Map, goal: (usize, usize)) -> &DistanceField {
+        let tick = self.tick;
+        let field = match self.fields.entry(goal) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                self.builds += 1;
+                entry.insert(Field {
+                    distances: DistanceField::towards(map, goal, Map::known_cost),
+                    changed: Vec::new(),
+                    used: tick,
+                })
+            }
+        };
+        if !field.distances.fits(map) {
+            field.changed.clear();
+            field.distances.rebuild(map);
+            self.builds += 1;
+        } else if !field.changed.is_empty() {
+            field.distances.update(map, field.changed.drain(..));
+        }
+        field.used = tick;
+        &field.distances
+    }
+
+    // Fonction pour noter des cases modifiées, dont les champs gardés tiendront compte
+    pub fn update(&mut self, cells: &[(usize, usize)]) {
+        for field in self.fields.values_mut() {
+            field.changed.extend_from_slice(cells);
+        }
+    }
+
+    // Fonction pour noter les cases découvertes autour d'une position
+    pub fn update_around(&mut self, map: &Map, (x, y): (usize, usize), range: usize) {
+        if self.fields.is_empty() {
+            return;
+        }
+        let cells: Vec<(usize, usize)> = (y.saturating_sub(range)
+            ..=(y + range).min(map.height - 1))
+            .flat_map(|y| {
+                (x.saturating_sub(range)..=(x + range).min(map.width - 1)).map(move |x| (x, y))
+            })
+            .collect();
+        self.update(&cells);
+    }
+
+    // Fonction pour compter les champs gardés
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    // Fonction pour savoir si aucun champ n'est gardé
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+}
+
+// Fonction pour trouver la case libre à la fin de la fenêtre la plus proche d'une cible, en
+// parcourant les cases par distance croissante à la cible ; la première dans l'ordre des
+// lignes à égalité
+fn nearest_free(
+    map: &Map,
+    reservations: &Reservations,
+    distances: &DistanceField,
+    goal: (usize, usize),
+    window: usize,
+) -> Option<(usize, usize)> {
+    let mut seen = HashSet::from([goal]);
+    let mut open = BinaryHeap::from([Reverse((0, goal.1, goal.0))]);
+    while let Some(Reverse((_, y, x))) = open.pop() {
+        if reservations.is_free((x, y), window) {
+            return Some((x, y));
+        }
+        for next in neighbors(map, (x, y)) {
+            let Some(distance) = distances.distance(next) else {
+                continue;
+            };
+            if seen.insert(next) {
+                open.push(Reverse((distance, next.1, next.0)));
+            }
+        }
+    }
+    None
+}
+
+// Fonction pour planifier le chemin d'un robot dans l'espace et le temps (A* coopératif à
+// fenêtre). Le robot peut attendre sur place ; le chemin renvoyé donne sa case à chaque tour,
+// de 0 à `window`, et s'arrête sur la cible si elle reste libre jusqu'à la fin de la fenêtre.
+// None si aucun chemin ne respecte les réservations.
+pub fn plan_path(
+    map: &Map,
+    reservations: &Reservations,
+    start: (usize, usize),
+    goal: (usize, usize),
+    window: usize,
+) -> Option<Vec<(usize, usize)>> {
+    let mut heuristics = Heuristics::default();
+    plan_path_with(&mut heuristics, map, reservations, start, goal, window)
+}
+
+// Fonction pour planifier le chemin d'un robot en réutilisant les champs de distances déjà
+// calculés vers sa cible
+pub fn plan_path_with(
+    heuristics: &mut Heuristics,
+    map: &Map,
+    reservations: &Reservations,
+    start: (usize, usize),
+    goal: (usize, usize),
+    window: usize,
+) -> Option<Vec<(usize, usize)>> {
+    // Une cible infranchissable n'est jamais atteinte, même si son champ n'est pas à jour
+    map.known_cost(goal)?;
+    let mut goal = goal;
+    let mut distances = heuristics.field(map, goal);
+    distances.distance(start)?;
+    // Une cible encore occupée à la fin de la fenêtre est remplacée par la case libre la plus
+    // proche, où le robot attendra son tour
+    if !reservations.is_free(goal, window) {
+        goal = nearest_free(map, reservations, distances, goal, window)?;
+        distances = heuristics.field(map, goal);
+    }
+    let heuristic = |cell: (usize, usize)| distances.distance(cell).unwrap_or(usize::MAX);
+
+    let mut parent: HashMap<State, State> = HashMap::new();
+    let mut best = HashMap::new();
+    let mut open = BinaryHeap::new();
+    best.insert((start, 0), 0);
+    open.push(Reverse((heuristic(start), 0, 0, start)));
+    let mut expansions = 0;
+
+    // État le plus proche de la cible, suivi si la recherche dépasse sa limite
+    let mut closest = (heuristic(start), start, 0);
+
+    while let Some(Reverse((_, cost, t, cell))) = open.pop() {
+        if best.get(&(cell, t)).is_some_and(|&known| cost > known) {
+            continue;
+        }
+        let arrived = cell == goal && (t..=window).all(|t| reservations.is_free(goal, t));
+        if arrived || t == window {
+            return Some(unwind(&parent, (cell, t), window));
+        }
+        if heuristic(cell) < closest.0 {
+            closest = (heuristic(cell), cell, t);
+        }
+        expansions += 1;
+        if expansions > MAX_EXPANSIONS {
+            let (_, cell, t) = closest;
+            return Some(unwind(&parent, (cell, t), window));
+        }
+
+        // Attendre sur place coûte un tour, avancer coûte le terrain de la case d'arrivée
+        let moves = neighbors(map, cell)
+            .filter_map(|next| Some((next, map.known_cost(next)?)))
+            .chain(std::iter::once((cell, 1)));
+        for (next, step) in moves {
+            if heuristic(next) == usize::MAX {
+                continue;
+            }
+            let allowed = if next == cell {
+                reservations.is_free(cell, t + 1)
+            } else {
+                reservations.can_move(cell, next, t)
+            };
+            let state = (next, t + 1);
+            if allowed && best.get(&state).is_none_or(|&known| cost + step < known) {
+                best.insert(state, cost + step);
+                parent.insert(state, (cell, t));
+                open.push(Reverse((
+                    cost + step + heuristic(next),
+                    cost + step,
+                    t + 1,
+                    next,
+                )));
+            }
+        }
+    }
+    None
+}
+
+// Fonction pour reconstituer un chemin jusqu'à un état, puis attendre sur place jusqu'à la fin
+// de la fenêtre
+fn unwind(parent: &HashMap<State, State>, mut state: State, window: usize) -> Vec<(usize, usize)> {
+    let mut path = vec![state.0];
+    while let Some(&previous) = parent.get(&state) {
+        path.push(previous.0);
+        state = previous;
+    }
+    path.reverse();
+    path.resize(window + 1, path[path.len() - 1]);
+    path
+}
+
+// Fonction pour savoir si un chemin respecte les réservations
+pub fn is_free_path(reservations: &Reservations, path: &[(usize, usize)]) -> bool {
+    path.windows(2)
+        .enumerate()
+        .all(|(t, step)| reservations.can_move(step[0], step[1], t))
+}
+
+// Interblocage signalé : des robots qui n'ont pas pu avancer depuis DEADLOCK_AFTER tours
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deadlock {
+    pub tick: usize,                // tour où l'interblocage est détecté
+    pub robots: Vec<usize>,         // indices des robots bloqués
+    pub cells: Vec<(usize, usize)>, // positions des robots bloqués
+}
+
+// Fonction pour trouver où un robot en interblocage peut reculer : une case voisine libre
+// pendant toute la fenêtre, la plus proche de sa cible
+fn back_off(
+    map: &Map,
+    reservations: &Reservations,
+    position: (usize, usize),
+    goal: (usize, usize),
+    window: usize,
+) -> Option<Vec<(usize, usize)>> {
+    neighbors(map, position)
+        .filter(|&next| next != goal && map.known_cost(next).is_some())
+        .map(|next| {
+            let mut path = vec![next; window + 1];
+            path[0] = position;
+            path
+        })
+        .filter(|path| is_free_path(reservations, path))
+        .min_by_key(|path| {
+            let (x, y) = path[1];
+            x.abs_diff(goal.0).max(y.abs_diff(goal.1))
+        })
+}
+
+// Planificateur de la flotte : chaque tour, les déplacements voulus par les robots sont
+// planifiés ensemble, par ordre de priorité, dans une table de réservation espace-temps. Un
+// robot ne suit que le premier pas de son chemin et la flotte est replanifiée au tour suivant.
+// Un robot en interblocage recule d'une case pour laisser passer les autres.
+#[derive(Debug)]
+pub struct FleetPlanner {
+    pub window: usize,            // nombre de tours planifiés à l'avance
+    pub deadlocks: Vec<Deadlock>, // interblocages détectés
+    pub unresolved: usize,        // recherches de chemin sans solution
+    pub backoffs: usize,          // reculs de robots en interblocage
+    pub heuristics: Heuristics,   // champs de distances vers les cibles, d'un tour à l'autre
+}
+
+impl Default for FleetPlanner {
+    fn default() -> Self {
+        FleetPlanner::new(PLAN_WINDOW)
+    }
+}
+
+impl FleetPlanner {
+    // Fonction pour créer un planificateur avec une fenêtre donnée
+    pub fn new(window: usize) -> Self {
+        FleetPlanner {
+            window: window.max(1),
+            deadlocks: Vec::new(),
+            unresolved: 0,
+            backoffs: 0,
+            heuristics: Heuristics::default(),
+        }
+    }
+
+    // Fonction pour coordonner les actions choisies par les robots : chaque déplacement est
+    // remplacé par le premier pas d'un chemin sans collision vers la cible du robot, ou par
+    // une attente, qui compte comme un tour bloqué. Le chemin prévu par le robot est gardé tant
+    // qu'il est libre. Les robots qui ne se déplacent pas sont des obstacles pour la fenêtre
+    // entière ; les robots bloqués depuis longtemps, puis ceux qui rentrent, passent en premier.
+    // Un robot resté bloqué DEADLOCK_AFTER tours recule vers une case voisine libre et oublie
+    // son chemin.
+    pub fn coordinate(&mut self, map: &Map, robots: &mut [Robot], actions: &mut [Action]) {
+        self.heuristics.next_tick();
+        let mut reservations = Reservations::new(map.base);
+        let mut moving = Vec::new();
+        for (index, (robot, action)) in robots.iter().zip(actions.iter()).enumerate() {
+            let position = (robot.x, robot.y);
+            match action {
+                Action::Move(direction) => {
+                    let Some(step) = direction.step(map, position) else {
+                        continue;
+                    };
+                    let goal = robot.route.target.filter(|&target| target != position);
+                    moving.push((index, goal.unwrap_or(step), step));
+                }
+                _ => reservations.reserve(&vec![position; self.window + 1]),
+            }
+        }
+        moving.sort_by_key(|&(index, _, _)| {
+            let robot = &robots[index];
+            (
+                Reverse(robot.blocked),
+                robot.state != RobotState::Returning,
+                index,
+            )
+        });
+
+        for (index, goal, step) in moving {
+            let robot = &mut robots[index];
+            let position = (robot.x, robot.y);
+
+            // Le chemin prévu par le robot est gardé s'il ne croise personne
+            let mut intended = vec![position];
+            if robot.route.target == Some(goal) && robot.route.path.first() == Some(&step) {
+                intended.extend(robot.route.path.iter().take(self.window));
+            } else {
+                intended.push(step);
+            }
+            intended.resize(self.window + 1, intended[intended.len() - 1]);
+            let mut path = if is_free_path(&reservations, &intended) {
+                Some(intended)
+            } else {
+                plan_path_with(
+                    &mut self.heuristics,
+                    map,
+                    &reservations,
+                    position,
+                    goal,
+                    self.window,
+                )
+            };
+            let stuck = path.as_ref().is_none_or(|path| path[1] == position);
+            if stuck && robot.blocked >= DEADLOCK_AFTER {
+                if let Some(aside) = back_off(map, &reservations, position, goal, self.window) {
+                    path = Some(aside);
+                    robot.route.path.clear();
+                    self.backoffs += 1;
+                }
+            }
+            let next = match &path {
+                Some(path) => path[1],
+                None => {
+                    self.unresolved += 1;
+                    position
+                }
+            };
+            reservations.reserve(&path.unwrap_or_else(|| vec![position; self.window + 1]));
+            match Direction::towards(position, next) {
+                Some(direction) => actions[index] = Action::Move(direction),
+                None => {
+                    actions[index] = Action::Wait;
+                    robot.blocked += 1;
+                }
+            }
+        }
+    }
+
+    // Fonction pour signaler les robots qui viennent de passer DEADLOCK_AFTER tours sans
+    // pouvoir avancer
+    pub fn detect_deadlocks(&mut self, robots: &[Robot], tick: usize) {
+        let stuck: Vec<(usize, (usize, usize))> = robots
+            .iter()
+            .enumerate()
+            .filter(|(_, robot)| robot.blocked == DEADLOCK_AFTER)
+            .map(|(index, robot)| (index, (robot.x, robot.y)))
+            .collect();
+        if !stuck.is_empty() {
+            self.deadlocks.push(Deadlock {
+                tick,
+                robots: stuck.iter().map(|&(index, _)| index).collect(),
+                cells: stuck.iter().map(|&(_, cell)| cell).collect(),
+            });
+        }
+    }
+}
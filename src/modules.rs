@@ -0,0 +1,232 @@
+// src/modules.rs
+use crate::resources::{Extraction, ENERGY_KEY, MINERAL_KEY};
+use crate::BATTERY_CAPACITY;
+use std::collections::BTreeMap;
+
+// Niveau maximal d'un module
+pub const MAX_LEVEL: usize = 3;
+
+// Types de modules qui peuvent équiper un châssis
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleKind {
+    Sensor,   // portée du capteur
+    Drill,    // vitesse de forage
+    Cargo,    // capacité de chargement
+    Battery,  // capacité de la batterie
+    Wheels,   // nombre de cases parcourues par tour
+    Analysis, // vitesse d'analyse des sites scientifiques
+}
+
+// Liste de tous les types de modules
+pub const ALL_MODULES: [ModuleKind; 6] = [
+    ModuleKind::Sensor,
+    ModuleKind::Drill,
+    ModuleKind::Cargo,
+    ModuleKind::Battery,
+    ModuleKind::Wheels,
+    ModuleKind::Analysis,
+];
+
+// Coût en ressources, payé par la base : la quantité de chaque ressource, désignée par son
+// mot-clé dans le registre
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Cost {
+    pub amounts: BTreeMap<String, usize>, // quantités non nulles, par mot-clé de ressource
+}
+
+impl Cost {
+    // Fonction pour créer un coût à partir de quantités par mot-clé
+    pub fn new(amounts: &[(&str, usize)]) -> Self {
+        let mut cost = Cost::default();
+        for &(key, amount) in amounts {
+            cost.add_amount(key, amount);
+        }
+        cost
+    }
+
+    // Fonction pour obtenir la quantité d'une ressource dans le coût
+    pub fn amount(&self, key: &str) -> usize {
+        self.amounts.get(key).copied().unwrap_or(0)
+    }
+
+    fn add_amount(&mut self, key: &str, amount: usize) {
+        if amount > 0 {
+            *self.amounts.entry(key.to_string()).or_insert(0) += amount;
+        }
+    }
+
+    // Fonction pour ajouter un autre coût à celui-ci
+    pub fn add(&mut self, other: &Cost) {
+        for (key, &amount) in &other.amounts {
+            self.add_amount(key, amount);
+        }
+    }
+}
+
+// Fonction pour obtenir le coût d'un châssis nu, sans aucun module
+pub fn chassis_cost() -> Cost {
+    Cost::new(&[(ENERGY_KEY, 1), (MINERAL_KEY, 2)])
+}
+
+// Niveau de chaque module monté sur un robot (0 si le module est absent)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Modules {
+    pub sensor: usize,
+    pub drill: usize,
+    pub cargo: usize,
+    pub battery: usize,
+    pub wheels: usize,
+    pub analysis: usize,
+}
+
+impl Modules {
+    // Equipement polyvalent des robots créés avec Robot::new
+    pub fn standard() -> Self {
+        Modules {
+            sensor: 1,
+            drill: MAX_LEVEL,
+            cargo: 1,
+            battery: 1,
+            wheels: 1,
+            analysis: 0,
+        }
+    }
+
+    // Eclaireur : grand capteur et roues rapides, sans foreuse ni soute
+    pub fn scout() -> Self {
+        Modules {
+            sensor: 2,
+            drill: 0,
+            cargo: 0,
+            battery: 2,
+            wheels: 2,
+            analysis: 0,
+        }
+    }
+
+    // Mineur : foreuse et soute, capteur minimal
+    pub fn miner() -> Self {
+        Modules {
+            sensor: 1,
+            drill: 2,
+            cargo: 2,
+            battery: 1,
+            wheels: 1,
+            analysis: 0,
+        }
+    }
+
+    // Chercheur : module d'analyse et petite soute pour rapporter les échantillons
+    pub fn researcher() -> Self {
+        Modules {
+            sensor: 1,
+            drill: 0,
+            cargo: 1,
+            battery: 1,
+            wheels: 1,
+            analysis: 2,
+        }
+    }
+
+    // Collecteur équipé du module qu'exige le mode d'extraction d'une ressource
+    pub fn collector(extraction: Extraction) -> Self {
+        match extraction {
+            Extraction::Drill => Modules::miner(),
+            Extraction::Analysis => Modules::researcher(),
+        }
+    }
+
+    // Fonction pour obtenir le niveau d'un module
+    pub fn level(&self, kind: ModuleKind) -> usize {
+        match kind {
+            ModuleKind::Sensor => self.sensor,
+            ModuleKind::Drill => self.drill,
+            ModuleKind::Cargo => self.cargo,
+            ModuleKind::Battery => self.battery,
+            ModuleKind::Wheels => self.wheels,
+            ModuleKind::Analysis => self.analysis,
+        }
+    }
+
+    fn level_mut(&mut self, kind: ModuleKind) -> &mut usize {
+        match kind {
+            ModuleKind::Sensor => &mut self.sensor,
+            ModuleKind::Drill => &mut self.drill,
+            ModuleKind::Cargo => &mut self.cargo,
+            ModuleKind::Battery => &mut self.battery,
+            ModuleKind::Wheels => &mut self.wheels,
+            ModuleKind::Analysis => &mut self.analysis,
+        }
+    }
+
+    // Fonction pour monter le niveau suivant d'un module, false si le niveau maximal est atteint
+    pub fn upgrade(&mut self, kind: ModuleKind) -> bool {
+        let level = self.level_mut(kind);
+        if *level >= MAX_LEVEL {
+            return false;
+        }
+        *level += 1;
+        true
+    }
+
+    // Fonction pour obtenir le rayon de cases révélées autour du robot
+    pub fn sensor_range(&self) -> usize {
+        self.sensor
+    }
+
+    // Fonction pour obtenir le nombre de tours de forage par unité, None sans foreuse
+    pub fn drill_ticks(&self) -> Option<usize> {
+        match self.drill {
+            0 => None,
+            level => Some(MAX_LEVEL + 1 - level.min(MAX_LEVEL)),
+        }
+    }
+
+    // Fonction pour obtenir le nombre de tours d'analyse par échantillon, None sans module d'analyse
+    pub fn analysis_ticks(&self) -> Option<usize> {
+        match self.analysis {
+            0 => None,
+            level => Some(2 * (MAX_LEVEL + 1 - level.min(MAX_LEVEL))),
+        }
+    }
+
+    // Fonction pour obtenir le nombre de tours par unité avec le module d'extraction donné
+    pub fn work_ticks(&self, extraction: Extraction) -> Option<usize> {
+        match extraction {
+            Extraction::Drill => self.drill_ticks(),
+            Extraction::Analysis => self.analysis_ticks(),
+        }
+    }
+
+    // Fonction pour obtenir le nombre d'unités que le robot peut transporter
+    pub fn cargo_capacity(&self) -> usize {
+        self.cargo
+    }
+
+    // Fonction pour obtenir la capacité de la batterie
+    pub fn battery_capacity(&self) -> usize {
+        BATTERY_CAPACITY / 2 * (self.battery + 1)
+    }
+
+    // Fonction pour obtenir le nombre de cases parcourues par tour
+    pub fn steps_per_tick(&self) -> usize {
+        self.wheels.max(1)
+    }
+
+    // Fonction pour obtenir le coût total du robot : châssis et niveaux de chaque module
+    pub fn cost(&self) -> Cost {
+        let mut total = chassis_cost();
+        for kind in ALL_MODULES {
+            for level in 1..=self.level(kind) {
+                total.add(&upgrade_cost(level));
+            }
+        }
+        total
+    }
+}
+
+// Fonction pour obtenir le coût d'un module au niveau donné : une unité d'énergie,
+// plus des minerais à partir du deuxième niveau
+pub fn upgrade_cost(level: usize) -> Cost {
+    Cost::new(&[(ENERGY_KEY, 1), (MINERAL_KEY, level.saturating_sub(1))])
+}
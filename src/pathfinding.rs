@@ -0,0 +1,229 @@
+// src/pathfinding.rs
+use crate::action::Direction;
+use crate::dstar::DStarLite;
+use crate::Map;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+// Déplacements possibles : les huit cases voisines
+pub const DIRECTIONS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+// Fonction pour obtenir les cases voisines d'une case, dans les limites de la carte
+pub fn neighbors(map: &Map, cell: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+    map.neighbors(cell)
+}
+
+// Fonction de coût d'une case pour la recherche de chemin, None si infranchissable
+pub type CostFn = fn(&Map, (usize, usize)) -> Option<usize>;
+
+// Fonction pour trouver le chemin le moins coûteux entre deux cases (A*) selon ce que les
+// robots savent de la carte. Le chemin renvoyé exclut la case de départ et inclut l'arrivée.
+pub fn find_path(
+    map: &Map,
+    start: (usize, usize),
+    goal: (usize, usize),
+) -> Option<Vec<(usize, usize)>> {
+    find_path_with(map, start, goal, Map::known_cost)
+}
+
+// Fonction pour trouver un chemin prudent, les cases inexplorées étant supposées difficiles
+pub fn find_safe_path(
+    map: &Map,
+    start: (usize, usize),
+    goal: (usize, usize),
+) -> Option<Vec<(usize, usize)>> {
+    find_path_with(map, start, goal, Map::safe_cost)
+}
+
+// Fonction pour trouver le chemin le moins coûteux (A*) avec une fonction de coût donnée
+pub fn find_path_with(
+    map: &Map,
+    start: (usize, usize),
+    goal: (usize, usize),
+    cost_of: CostFn,
+) -> Option<Vec<(usize, usize)>> {
+    if start == goal {
+        return Some(Vec::new());
+    }
+    cost_of(map, goal)?;
+
+    let index = |(x, y): (usize, usize)| y * map.width + x;
+    let heuristic = |(x, y): (usize, usize)| x.abs_diff(goal.0).max(y.abs_diff(goal.1));
+
+    let mut best = vec![usize::MAX; map.width * map.height];
+    let mut parent = vec![usize::MAX; map.width * map.height];
+    let mut open = BinaryHeap::new();
+    best[index(start)] = 0;
+    open.push(Reverse((heuristic(start), 0, start)));
+
+    while let Some(Reverse((_, cost, cell))) = open.pop() {
+        if cell == goal {
+            let mut path = vec![goal];
+            let mut current = index(goal);
+            while parent[current] != index(start) {
+                current = parent[current];
+                path.push((current % map.width, current / map.width));
+            }
+            path.reverse();
+            return Some(path);
+        }
+        if cost > best[index(cell)] {
+            continue;
+        }
+        for next in neighbors(map, cell) {
+            let Some(step) = cost_of(map, next) else {
+                continue;
+            };
+            let next_cost = cost + step;
+            if next_cost < best[index(next)] {
+                best[index(next)] = next_cost;
+                parent[index(next)] = index(cell);
+                open.push(Reverse((next_cost + heuristic(next), next_cost, next)));
+            }
+        }
+    }
+
+    None
+}
+
+// Fonction pour calculer le coût total d'un chemin selon ce que les robots savent de la carte
+pub fn path_cost(map: &Map, path: &[(usize, usize)]) -> usize {
+    path_cost_with(map, path, Map::known_cost)
+}
+
+// Fonction pour calculer le coût total d'un chemin avec une fonction de coût donnée
+pub fn path_cost_with(map: &Map, path: &[(usize, usize)], cost_of: CostFn) -> usize {
+    path.iter()
+        .map(|&cell| cost_of(map, cell).unwrap_or(0))
+        .sum()
+}
+
+// Compteurs de replanification d'un itinéraire
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ReplanStats {
+    pub searches: usize,   // recherches lancées pour une nouvelle cible
+    pub repairs: usize,    // réparations après la découverte d'un coût différent sur le chemin
+    pub expansions: usize, // cases développées par les recherches et les réparations
+}
+
+impl ReplanStats {
+    // Fonction pour cumuler les compteurs de plusieurs itinéraires
+    pub fn add(&mut self, other: ReplanStats) {
+        self.searches += other.searches;
+        self.repairs += other.repairs;
+        self.expansions += other.expansions;
+    }
+}
+
+// Itinéraire mémorisé par un robot : la cible visée, le chemin qui y mène et la recherche
+// incrémentale qui l'a produit
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Route {
+    pub target: Option<(usize, usize)>, // dernière cible visée
+    pub path: Vec<(usize, usize)>,      // cases restant à parcourir jusqu'à la cible
+    pub search: Option<Box<DStarLite>>, // recherche en cours vers la cible
+    pub stats: ReplanStats,             // compteurs de replanification
+}
+
+impl Route {
+    // Fonction pour oublier l'itinéraire en cours ; les compteurs sont gardés
+    pub fn clear(&mut self) {
+        self.target = None;
+        self.path.clear();
+        self.search = None;
+    }
+
+    // Fonction pour obtenir la direction du prochain pas vers une cible. La recherche vers la
+    // cible est gardée d'un pas à l'autre ; quand une case du chemin se révèle d'un autre coût
+    // que prévu, elle est réparée au lieu d'être refaite. None si le robot est déjà sur la
+    // cible ou si aucun chemin n'existe.
+    pub fn next_step(
+        &mut self,
+        map: &Map,
+        from: (usize, usize),
+        target: (usize, usize),
+        cost_of: CostFn,
+    ) -> Option<Direction> {
+        if self.target != Some(target) {
+            self.clear();
+        }
+        self.target = Some(target);
+        if from == target {
+            self.path.clear();
+            return None;
+        }
+        if self.search.as_ref().is_some_and(|search| !search.fits(map)) {
+            self.search = None;
+        }
+        let search = self.search.get_or_insert_with(|| {
+            self.stats.searches += 1;
+            Box::new(DStarLite::new(map, from, target, cost_of))
+        });
+        search.move_to(from);
+        loop {
+            self.stats.expansions += search.compute(map, cost_of);
+            let path = search.path(map);
+            let changed: Vec<(usize, usize)> = path
+                .iter()
+                .copied()
+                .filter(|&cell| search.cost(cell) != cost_of(map, cell))
+                .collect();
+            if changed.is_empty() {
+                self.path = path;
+                break;
+            }
+            search.update_costs(map, &changed, cost_of);
+            self.stats.repairs += 1;
+        }
+        Direction::towards(from, *self.path.first()?)
+    }
+
+    // Fonction pour obtenir la direction du prochain pas vers une cible avec un planificateur
+    // qui renvoie un chemin complet. Le chemin mémorisé est suivi tant qu'il reste praticable ;
+    // sinon il est redemandé au planificateur, ce qui compte comme une réparation pour la
+    // même cible.
+    pub fn follow(
+        &mut self,
+        map: &Map,
+        from: (usize, usize),
+        target: (usize, usize),
+        cost_of: CostFn,
+        plan: impl FnOnce() -> Option<Vec<(usize, usize)>>,
+    ) -> Option<Direction> {
+        let fresh = self.target != Some(target);
+        if fresh {
+            self.clear();
+        }
+        self.target = Some(target);
+        if from == target {
+            self.path.clear();
+            return None;
+        }
+        if let Some(index) = self.path.iter().position(|&cell| cell == from) {
+            self.path.drain(..=index);
+        }
+        if self
+            .path
+            .first()
+            .is_none_or(|&next| Direction::towards(from, next).is_none())
+            || self.path.iter().any(|&cell| cost_of(map, cell).is_none())
+        {
+            if fresh {
+                self.stats.searches += 1;
+            } else {
+                self.stats.repairs += 1;
+            }
+            self.path = plan().unwrap_or_default();
+        }
+        Direction::towards(from, *self.path.first()?)
+    }
+}
@@ -0,0 +1,236 @@
+// src/resources.rs
+use std::fs;
+use std::io::{self, Error, ErrorKind};
+use std::path::Path;
+
+// Identifiant d'un type de ressource : sa position dans le registre de la carte
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ResourceKind(pub usize);
+
+// Types de ressources du registre standard, dans son ordre. Un registre chargé d'un fichier
+// peut les ranger autrement : le code de la simulation les retrouve par leur mot-clé.
+pub const ENERGY: ResourceKind = ResourceKind(0);
+pub const MINERALS: ResourceKind = ResourceKind(1);
+pub const SCIENCE: ResourceKind = ResourceKind(2);
+
+// Mots-clés des ressources qui paient les robots et leurs modules ou qu'équipe la flotte de départ
+pub const ENERGY_KEY: &str = "energy";
+pub const MINERAL_KEY: &str = "mineral";
+pub const SCIENCE_KEY: &str = "science";
+
+// Module utilisé pour collecter une ressource
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Extraction {
+    Drill,    // forage d'un gisement
+    Analysis, // analyse d'un site scientifique
+}
+
+// Description d'un type de ressource
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceDef {
+    pub key: String,            // mot-clé utilisé dans les fichiers de carte
+    pub name: String,           // nom affiché dans l'inspecteur
+    pub symbol: char,           // symbole du format ASCII (en minuscule sur un obstacle)
+    pub color: u32,             // couleur dans draw_map
+    pub rarity: usize,          // generate_map place un gisement pour `rarity` cases libres
+    pub min_distance: usize,    // distance minimale (Manhattan) entre un gisement et la base
+    pub extraction: Extraction, // module nécessaire pour la collecte
+    pub regeneration: usize,    // tours avant qu'un gisement épuisé réapparaisse, 0 si épuisable
+    pub solar: bool,            // la régénération n'avance qu'en plein jour
+}
+
+// Registre des types de ressources d'une carte
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ResourceRegistry {
+    kinds: Vec<ResourceDef>,
+}
+
+// Format texte d'un registre, une ressource par ligne :
+//
+//   resource <clé> symbol=<car> color=<RRGGBB> rarity=<n> distance=<n> tool=<drill|analysis>
+//            regen=<tours> solar=<yes|no> name=<nom>
+//
+// Seule la clé est obligatoire. Les lignes vides et celles commençant par '#' sont ignorées.
+
+impl ResourceRegistry {
+    // Fonction pour créer un registre vide
+    pub fn new() -> Self {
+        ResourceRegistry::default()
+    }
+
+    // Registre standard : énergie renouvelable (solaire), minerais et sites scientifiques
+    pub fn standard() -> Self {
+        let mut registry = ResourceRegistry::new();
+        registry.register(ResourceDef {
+            regeneration: 150,
+            solar: true,
+            ..ResourceDef::new(ENERGY_KEY, "énergie", 'E', 0xFF_00FF00)
+        });
+        registry.register(ResourceDef::new(MINERAL_KEY, "minerais", 'M', 0xFFFF0000));
+        registry.register(ResourceDef {
+            rarity: 180,
+            min_distance: 6,
+            extraction: Extraction::Analysis,
+            ..ResourceDef::new(SCIENCE_KEY, "science", 'S', 0xFF_AA00FF)
+        });
+        registry
+    }
+
+    // Fonction pour ajouter un type de ressource au registre
+    pub fn register(&mut self, def: ResourceDef) -> ResourceKind {
+        self.kinds.push(def);
+        ResourceKind(self.kinds.len() - 1)
+    }
+
+    // Fonction pour obtenir la description d'un type de ressource
+    pub fn get(&self, kind: ResourceKind) -> &ResourceDef {
+        &self.kinds[kind.0]
+    }
+
+    // Fonction pour obtenir le nombre de types de ressources
+    pub fn len(&self) -> usize {
+        self.kinds.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.kinds.is_empty()
+    }
+
+    // Fonction pour parcourir les types de ressources dans l'ordre du registre
+    pub fn kinds(&self) -> impl Iterator<Item = ResourceKind> {
+        (0..self.kinds.len()).map(ResourceKind)
+    }
+
+    // Fonction pour retrouver un type de ressource par son mot-clé
+    pub fn by_key(&self, key: &str) -> Option<ResourceKind> {
+        self.kinds
+            .iter()
+            .position(|def| def.key == key)
+            .map(ResourceKind)
+    }
+
+    // Fonction pour retrouver un type de ressource par son symbole ASCII, en majuscule ou non
+    pub fn by_symbol(&self, symbol: char) -> Option<ResourceKind> {
+        self.kinds
+            .iter()
+            .position(|def| def.symbol == symbol.to_ascii_uppercase())
+            .map(ResourceKind)
+    }
+}
+
+impl ResourceDef {
+    // Fonction pour décrire une ressource forée, avec les paramètres de génération par défaut
+    pub fn new(key: &str, name: &str, symbol: char, color: u32) -> Self {
+        ResourceDef {
+            key: key.to_string(),
+            name: name.to_string(),
+            symbol,
+            color,
+            rarity: 90,
+            min_distance: 0,
+            extraction: Extraction::Drill,
+            regeneration: 0,
+            solar: false,
+        }
+    }
+}
+
+// Fonction pour lire un registre depuis du texte
+pub fn registry_from_str(text: &str) -> io::Result<ResourceRegistry> {
+    let mut registry = ResourceRegistry::new();
+
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut words = line.split_whitespace();
+        let key = match (words.next(), words.next()) {
+            (Some("resource"), Some(key)) => key,
+            _ => return Err(invalid(number + 1, "'resource <clé>' attendu")),
+        };
+        if registry.by_key(key).is_some() {
+            return Err(invalid(number + 1, "ressource déjà définie"));
+        }
+
+        let symbol = key.chars().next().unwrap_or('?').to_ascii_uppercase();
+        let mut def = ResourceDef::new(key, key, symbol, 0xFF_FFFFFF);
+        for word in words {
+            let (name, value) = word
+                .split_once('=')
+                .ok_or_else(|| invalid(number + 1, "'nom=valeur' attendu"))?;
+            match name {
+                "symbol" => def.symbol = parse_symbol(value, number + 1)?,
+                "color" => {
+                    def.color = 0xFF00_0000
+                        | u32::from_str_radix(value, 16)
+                            .map_err(|_| invalid(number + 1, "couleur invalide"))?
+                }
+                "rarity" => def.rarity = parse_value(value, number + 1)?.max(1),
+                "distance" => def.min_distance = parse_value(value, number + 1)?,
+                "tool" => {
+                    def.extraction = match value {
+                        "drill" => Extraction::Drill,
+                        "analysis" => Extraction::Analysis,
+                        _ => return Err(invalid(number + 1, "outil inconnu")),
+                    }
+                }
+                "regen" => def.regeneration = parse_value(value, number + 1)?,
+                "solar" => {
+                    def.solar = match value {
+                        "yes" => true,
+                        "no" => false,
+                        _ => return Err(invalid(number + 1, "'yes' ou 'no' attendu")),
+                    }
+                }
+                "name" => def.name = value.to_string(),
+                other => {
+                    return Err(invalid(
+                        number + 1,
+                        &format!("paramètre inconnu '{}'", other),
+                    ))
+                }
+            }
+        }
+        if !is_resource_symbol(def.symbol) {
+            return Err(invalid(number + 1, "symbole invalide"));
+        }
+        if registry.by_symbol(def.symbol).is_some() {
+            return Err(invalid(number + 1, "symbole déjà utilisé"));
+        }
+        registry.register(def);
+    }
+
+    Ok(registry)
+}
+
+// Fonction pour charger un registre depuis un fichier
+pub fn load_registry(path: impl AsRef<Path>) -> io::Result<ResourceRegistry> {
+    registry_from_str(&fs::read_to_string(path)?)
+}
+
+fn parse_symbol(value: &str, line: usize) -> io::Result<char> {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(symbol), None) => Ok(symbol.to_ascii_uppercase()),
+        _ => Err(invalid(line, "symbole invalide")),
+    }
+}
+
+// Les symboles du terrain et de la base sont réservés au format ASCII, et un gisement sur
+// un obstacle s'écrit en minuscule : seules les lettres autres que 'B' conviennent
+fn is_resource_symbol(symbol: char) -> bool {
+    symbol.is_ascii_uppercase() && symbol != 'B'
+}
+
+fn parse_value(value: &str, line: usize) -> io::Result<usize> {
+    value.parse().map_err(|_| invalid(line, "nombre invalide"))
+}
+
+fn invalid(line: usize, message: &str) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!("ligne {} : {}", line, message),
+    )
+}
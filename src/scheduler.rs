@@ -0,0 +1,281 @@
+// src/scheduler.rs
+use crate::auction::AuctionPolicy;
+use crate::base::Base;
+use crate::clock::Clock;
+use crate::resources::ResourceKind;
+use crate::{Map, Robot, RobotState, Task};
+use std::fmt;
+
+// Nombre de tours entre deux passages du planificateur
+pub const SCHEDULER_PERIOD: usize = 20;
+
+// Politique de répartition des tâches : une tâche par robot, dans l'ordre de la flotte
+pub trait Policy: fmt::Debug {
+    // Fonction pour nommer la politique
+    fn name(&self) -> &str;
+
+    // Fonction pour choisir la tâche de chaque robot
+    fn assign(&self, map: &Map, robots: &[Robot], base: &Base) -> Vec<Task>;
+
+    // Fonction pour choisir la tâche de chaque robot et, s'il y a lieu, la case qu'il doit
+    // viser : une case à explorer, un gisement à collecter ou la base pour y livrer sa
+    // cargaison. Par défaut, aucune case n'est imposée.
+    fn assign_targets(
+        &self,
+        map: &Map,
+        robots: &[Robot],
+        base: &Base,
+    ) -> Vec<(Task, Option<(usize, usize)>)> {
+        self.assign(map, robots, base)
+            .into_iter()
+            .map(|task| (task, None))
+            .collect()
+    }
+}
+
+// Planificateur de la base : applique périodiquement une politique à la flotte
+#[derive(Debug)]
+pub struct Scheduler {
+    pub policy: Box<dyn Policy>, // politique utilisée
+    pub period: usize,           // tours entre deux répartitions
+}
+
+impl Scheduler {
+    // Fonction pour créer un planificateur
+    pub fn new(policy: Box<dyn Policy>, period: usize) -> Self {
+        Scheduler {
+            policy,
+            period: period.max(1),
+        }
+    }
+
+    // Fonction pour savoir si le planificateur doit passer pendant ce tour
+    pub fn is_due(&self, clock: &Clock) -> bool {
+        clock.tick.is_multiple_of(self.period)
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Scheduler::new(Box::new(BalancedPolicy), SCHEDULER_PERIOD)
+    }
+}
+
+// Politique fixe : chaque robot garde la tâche reçue à sa construction
+#[derive(Debug)]
+pub struct FixedPolicy;
+
+impl Policy for FixedPolicy {
+    fn name(&self) -> &str {
+        "fixed"
+    }
+
+    fn assign(&self, _map: &Map, robots: &[Robot], _base: &Base) -> Vec<Task> {
+        robots.iter().map(|robot| robot.task).collect()
+    }
+}
+
+// Politique équilibrée : des explorateurs en proportion de la carte inconnue, puis les
+// collecteurs répartis selon les gisements connus de chaque ressource, une ressource déjà
+// abondante dans le stock de la base étant moins demandée
+#[derive(Debug)]
+pub struct BalancedPolicy;
+
+impl Policy for BalancedPolicy {
+    fn name(&self) -> &str {
+        "balanced"
+    }
+
+    fn assign(&self, map: &Map, robots: &[Robot], base: &Base) -> Vec<Task> {
+        let cells = map.width * map.height;
+        let unexplored = base.frontier.count(map);
+
+        // Quotas d'explorateurs puis de collecteurs
+        let mut quotas: Vec<(Task, usize)> = Vec::new();
+        let explorers = if unexplored > 0 {
+            (robots.len() * unexplored).div_ceil(cells).max(1)
+        } else {
+            0
+        };
+        quotas.push((Task::Explore, explorers.min(robots.len())));
+
+        let weights: Vec<(ResourceKind, f64)> = map
+            .resources
+            .kinds()
+            .map(|kind| {
+                let known = map
+                    .deposits(kind)
+                    .iter()
+                    .filter(|&&(x, y)| map.is_explored((x, y)))
+                    .count();
+                (kind, known as f64 / (1 + base.stock(kind)) as f64)
+            })
+            .filter(|&(_, weight)| weight > 0.0)
+            .collect();
+        let mut collectors = vec![0; weights.len()];
+        for _ in explorers.min(robots.len())..robots.len() {
+            // Répartition proportionnelle par la méthode des plus forts quotients
+            let best = (0..weights.len()).max_by(|&a, &b| {
+                let quotient = |i: usize| weights[i].1 / (1 + collectors[i]) as f64;
+                quotient(a).total_cmp(&quotient(b)).then(b.cmp(&a))
+            });
+            match best {
+                Some(i) => collectors[i] += 1,
+                None => break,
+            }
+        }
+        for (i, &(kind, _)) in weights.iter().enumerate() {
+            quotas.push((Task::Collect(kind), collectors[i]));
+        }
+
+        // Les robots gardent leur tâche tant que son quota n'est pas atteint, les autres
+        // prennent la tâche au plus grand quota restant qu'ils savent faire
+        let mut tasks: Vec<Option<Task>> = vec![None; robots.len()];
+        for (robot, task) in robots.iter().zip(tasks.iter_mut()) {
+            if let Some(quota) = quotas
+                .iter_mut()
+                .find(|(candidate, quota)| *candidate == robot.task && *quota > 0)
+            {
+                quota.1 -= 1;
+                *task = Some(robot.task);
+            }
+        }
+        for (robot, task) in robots.iter().zip(tasks.iter_mut()) {
+            if task.is_some() {
+                continue;
+            }
+            let choice = quotas
+                .iter_mut()
+                .filter(|(candidate, quota)| *quota > 0 && can_perform(map, robot, *candidate))
+                .max_by_key(|(_, quota)| *quota);
+            *task = Some(match choice {
+                Some(quota) => {
+                    quota.1 -= 1;
+                    quota.0
+                }
+                None => robot.task,
+            });
+        }
+        tasks.into_iter().flatten().collect()
+    }
+}
+
+// Fonction pour savoir si les modules d'un robot lui permettent d'accomplir une tâche
+pub fn can_perform(map: &Map, robot: &Robot, task: Task) -> bool {
+    match task {
+        Task::Explore => true,
+        Task::Collect(kind) => {
+            robot
+                .modules
+                .work_ticks(map.resources.get(kind).extraction)
+                .is_some()
+                && robot.modules.cargo_capacity() > 0
+        }
+    }
+}
+
+// Fonction pour confier une tâche à un robot et accorder son état : un robot qui rentre à la
+// base termine son retour, un collecteur ne quitte l'exploration que si un gisement de sa
+// ressource est connu. Renvoie true si la tâche a changé.
+pub fn reassign(map: &Map, robot: &mut Robot, task: Task) -> bool {
+    let changed = robot.task != task;
+    if changed {
+        robot.task = task;
+        robot.drill_progress = 0;
+        robot.route.clear();
+        robot.tour.clear();
+        robot.goal = None;
+    }
+    if robot.state != RobotState::Returning {
+        match task {
+            Task::Explore => robot.state = RobotState::Exploring,
+            Task::Collect(kind) => {
+                if map
+                    .deposits(kind)
+                    .iter()
+                    .any(|&(x, y)| map.is_explored((x, y)))
+                {
+                    robot.state = RobotState::Collecting;
+                }
+            }
+        }
+    }
+    changed
+}
+
+// Fonction pour diriger un robot vers la case choisie pour lui : la base le fait rentrer
+// livrer, un gisement de sa tâche lui est réservé et devient la première étape de sa
+// tournée, une autre case devient la case à explorer qu'il vise. Les autres étapes de la
+// tournée restent réservées au robot, la dernière étant retirée si la tournée s'allonge.
+pub fn direct(
+    map: &Map,
+    robot: &mut Robot,
+    index: usize,
+    target: (usize, usize),
+    base: &mut Base,
+    clock: &Clock,
+) {
+    if target == map.base {
+        robot.state = RobotState::Returning;
+        return;
+    }
+    match robot.task {
+        Task::Collect(kind) if map.deposit_at(target) == Some(kind) => {
+            if base
+                .claims
+                .owner(target)
+                .is_some_and(|owner| owner != index)
+            {
+                return;
+            }
+            if robot.tour.first() != Some(&target) {
+                let stops = robot.tour.len().max(1);
+                robot.tour.retain(|&stop| stop != target);
+                robot.tour.insert(0, target);
+                robot.tour.truncate(stops);
+                robot.route.clear();
+            }
+            base.claims
+                .claim_all(robot.tour.iter().copied(), index, clock.tick);
+        }
+        Task::Collect(_) => {}
+        Task::Explore => {
+            if robot.goal != Some(target) {
+                robot.goal = Some(target);
+                robot.route.clear();
+            }
+        }
+    }
+}
+
+// Fonction pour appliquer la politique de la base à la flotte, si c'est le moment ;
+// renvoie le nombre de robots réaffectés
+pub fn schedule(map: &Map, robots: &mut [Robot], base: &mut Base, clock: &Clock) -> usize {
+    if !base.scheduler.is_due(clock) {
+        return 0;
+    }
+    let assignments = base.scheduler.policy.assign_targets(map, robots, base);
+    let mut changed = 0;
+    for (index, (robot, (task, target))) in robots.iter_mut().zip(assignments).enumerate() {
+        if !can_perform(map, robot, task) {
+            continue;
+        }
+        if reassign(map, robot, task) {
+            changed += 1;
+        }
+        if let Some(target) = target {
+            direct(map, robot, index, target, base, clock);
+        }
+    }
+    changed
+}
+
+// Fonction pour créer une politique à partir de son nom
+pub fn policy_from_name(name: &str) -> Option<Box<dyn Policy>> {
+    match name {
+        "fixed" => Some(Box::new(FixedPolicy)),
+        "balanced" => Some(Box::new(BalancedPolicy)),
+        "auction" => Some(Box::new(AuctionPolicy)),
+        _ => None,
+    }
+}
@@ -0,0 +1,292 @@
+// src/script.rs
+use crate::action::{Action, Direction};
+use crate::behavior::{self, Behavior, Knowledge, Memory, RobotView};
+use crate::{RobotState, Task};
+use rhai::{Array, Dynamic, Engine, Map as ScriptMap, Scope, AST};
+use std::fs;
+use std::io::{self, Error, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+// Un script de robot définit une fonction appelée à chaque tour :
+//
+//   fn decide(robot, world) { ... }
+//
+// robot : #{ x, y, battery, battery_capacity, cargo, cargo_capacity, task, resource, state,
+//            at_base, needs_recharge, can_collect, sensor_range }
+// world : #{ width, height, base_x, base_y, day, explored, deposit, cells }
+//         deposit est le gisement connu le plus proche de la ressource du robot (#{ x, y }
+//         ou ()), cells les cases à portée du capteur (#{ x, y, explored, terrain, deposit })
+//
+// La fonction renvoie une action élémentaire : une direction ("north", "northeast", "east",
+// ..., "northwest"), "mine", "unload", "recharge" ou "wait". Elle peut aussi déléguer le
+// pas du tour aux stratégies intégrées : "explore", "collect", "return" ou
+// #{ action: "travel", x: <x>, y: <y> }. Les scripts n'ont accès à aucun fichier et leur
+// exécution est bornée ; en cas d'erreur, le robot suit la stratégie de sa tâche.
+
+// Nombre maximal d'opérations d'un appel de script
+const MAX_OPERATIONS: u64 = 100_000;
+
+// Stratégie pilotée par un script Rhai, rechargé dès que son fichier change
+pub struct ScriptBehavior {
+    engine: Engine,               // interpréteur bridé
+    ast: AST,                     // script compilé
+    path: Option<PathBuf>,        // fichier du script, surveillé pour le rechargement
+    modified: Option<SystemTime>, // date de modification du script chargé
+    memory: Memory,               // état et itinéraire des actions déléguées
+    pub error: Option<String>,    // dernière erreur de compilation ou d'exécution
+}
+
+impl ScriptBehavior {
+    // Fonction pour compiler un script depuis du texte
+    pub fn from_source(source: &str) -> io::Result<Self> {
+        let engine = sandboxed_engine();
+        let ast = compile(&engine, source)?;
+        Ok(ScriptBehavior {
+            engine,
+            ast,
+            path: None,
+            modified: None,
+            memory: Memory::default(),
+            error: None,
+        })
+    }
+
+    // Fonction pour charger un script depuis un fichier
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut script = ScriptBehavior::from_source(&fs::read_to_string(path)?)?;
+        script.modified = modified(path);
+        script.path = Some(path.to_path_buf());
+        Ok(script)
+    }
+
+    // Fonction pour recharger le script si son fichier a changé ; un script invalide est
+    // signalé et l'ancien reste en service
+    pub fn reload_if_changed(&mut self) -> bool {
+        let Some(path) = &self.path else {
+            return false;
+        };
+        let stamp = modified(path);
+        if stamp.is_none() || stamp == self.modified {
+            return false;
+        }
+        self.modified = stamp;
+        let compiled = fs::read_to_string(path).and_then(|source| compile(&self.engine, &source));
+        match compiled {
+            Ok(ast) => {
+                self.ast = ast;
+                self.error = None;
+                true
+            }
+            Err(e) => {
+                self.error = Some(e.to_string());
+                false
+            }
+        }
+    }
+
+    fn run(&mut self, robot: &RobotView, knowledge: &Knowledge) -> Result<Action, String> {
+        // L'état vu par le script est celui des actions qu'il a déléguées
+        let robot = &RobotView {
+            state: self.memory.state,
+            ..robot.clone()
+        };
+        let result: Dynamic = self
+            .engine
+            .call_fn(
+                &mut Scope::new(),
+                &self.ast,
+                "decide",
+                (
+                    robot_to_script(robot, knowledge),
+                    world_to_script(robot, knowledge),
+                ),
+            )
+            .map_err(|e| e.to_string())?;
+        action_from_script(result, &mut self.memory, robot, knowledge)
+    }
+}
+
+impl Behavior for ScriptBehavior {
+    fn name(&self) -> &str {
+        "script"
+    }
+
+    fn decide(&mut self, robot: &RobotView, knowledge: &Knowledge) -> Action {
+        self.reload_if_changed();
+        match self.run(robot, knowledge) {
+            Ok(action) => action,
+            Err(e) => {
+                self.error = Some(e);
+                behavior::decide_by_task(&mut self.memory, robot, knowledge)
+            }
+        }
+    }
+}
+
+// Fonction pour créer un interpréteur sans accès au système et aux ressources bornées
+fn sandboxed_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_call_levels(32)
+        .set_max_expr_depths(64, 32)
+        .set_max_string_size(1_000)
+        .set_max_array_size(10_000)
+        .set_max_map_size(100)
+        .set_max_modules(0)
+        .on_print(|_| {})
+        .on_debug(|_, _, _| {});
+    engine.disable_symbol("eval");
+    engine
+}
+
+fn compile(engine: &Engine, source: &str) -> io::Result<AST> {
+    let ast = engine
+        .compile(source)
+        .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+    if !ast
+        .iter_functions()
+        .any(|f| f.name == "decide" && f.params.len() == 2)
+    {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "fonction 'decide(robot, world)' attendue",
+        ));
+    }
+    Ok(ast)
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+fn robot_to_script(robot: &RobotView, knowledge: &Knowledge) -> ScriptMap {
+    let mut map = ScriptMap::new();
+    map.insert("x".into(), (robot.position.0 as i64).into());
+    map.insert("y".into(), (robot.position.1 as i64).into());
+    map.insert("battery".into(), (robot.battery as i64).into());
+    map.insert(
+        "battery_capacity".into(),
+        (robot.battery_capacity as i64).into(),
+    );
+    map.insert("cargo".into(), (robot.cargo as i64).into());
+    map.insert(
+        "cargo_capacity".into(),
+        (robot.cargo_capacity as i64).into(),
+    );
+    let (task, resource) = match robot.task {
+        Task::Explore => ("explore", Dynamic::UNIT),
+        Task::Collect(kind) => (
+            "collect",
+            knowledge.map.resources.get(kind).key.clone().into(),
+        ),
+    };
+    map.insert("task".into(), task.into());
+    map.insert("resource".into(), resource);
+    let state = match robot.state {
+        RobotState::Exploring => "exploring",
+        RobotState::Returning => "returning",
+        RobotState::Collecting => "collecting",
+    };
+    map.insert("state".into(), state.into());
+    map.insert("at_base".into(), robot.at_base.into());
+    map.insert("needs_recharge".into(), robot.needs_recharge.into());
+    map.insert("can_collect".into(), robot.can_collect().into());
+    map.insert("sensor_range".into(), (robot.sensor_range as i64).into());
+    map
+}
+
+fn world_to_script(robot: &RobotView, knowledge: &Knowledge) -> ScriptMap {
+    let map = knowledge.map;
+    let mut world = ScriptMap::new();
+    world.insert("width".into(), (map.width as i64).into());
+    world.insert("height".into(), (map.height as i64).into());
+    world.insert("base_x".into(), (map.base.0 as i64).into());
+    world.insert("base_y".into(), (map.base.1 as i64).into());
+    world.insert("day".into(), knowledge.clock.is_day().into());
+    world.insert("explored".into(), knowledge.fully_explored().into());
+    let deposit = match robot.task {
+        Task::Collect(kind) => knowledge.nearest_deposit(kind, robot.position),
+        Task::Explore => None,
+    };
+    world.insert(
+        "deposit".into(),
+        deposit.map_or(Dynamic::UNIT, |(x, y)| position_to_script(x, y).into()),
+    );
+
+    // Environnement local : les cases à portée du capteur
+    let (rx, ry) = robot.position;
+    let range = robot.sensor_range;
+    let mut cells = Array::new();
+    for y in ry.saturating_sub(range)..=(ry + range).min(map.height - 1) {
+        for x in rx.saturating_sub(range)..=(rx + range).min(map.width - 1) {
+            let mut cell = position_to_script(x, y);
+            let explored = map.is_explored((x, y));
+            cell.insert("explored".into(), explored.into());
+            let (terrain, deposit) = if explored {
+                (
+                    format!("{:?}", map.terrain_at((x, y)))
+                        .to_lowercase()
+                        .into(),
+                    map.deposit_at((x, y)).map_or(Dynamic::UNIT, |kind| {
+                        map.resources.get(kind).key.clone().into()
+                    }),
+                )
+            } else {
+                (Dynamic::UNIT, Dynamic::UNIT)
+            };
+            cell.insert("terrain".into(), terrain);
+            cell.insert("deposit".into(), deposit);
+            cells.push(cell.into());
+        }
+    }
+    world.insert("cells".into(), cells.into());
+    world
+}
+
+fn position_to_script(x: usize, y: usize) -> ScriptMap {
+    let mut position = ScriptMap::new();
+    position.insert("x".into(), (x as i64).into());
+    position.insert("y".into(), (y as i64).into());
+    position
+}
+
+fn action_from_script(
+    result: Dynamic,
+    memory: &mut Memory,
+    robot: &RobotView,
+    knowledge: &Knowledge,
+) -> Result<Action, String> {
+    if let Some(map) = result.clone().try_cast::<ScriptMap>() {
+        let action = map.get("action").map(|value| value.to_string());
+        if action.as_deref() != Some("travel") {
+            return Err("#{ action: \"travel\", x, y } attendu".to_string());
+        }
+        let coordinate = |name: &str, limit: usize| {
+            map.get(name)
+                .and_then(|value| value.as_int().ok())
+                .filter(|&value| value >= 0 && (value as usize) < limit)
+                .map(|value| value as usize)
+                .ok_or_else(|| format!("coordonnée '{}' invalide", name))
+        };
+        let x = coordinate("x", knowledge.map.width)?;
+        let y = coordinate("y", knowledge.map.height)?;
+        return behavior::travel(memory, robot, knowledge, (x, y))
+            .ok_or_else(|| format!("aucun chemin vers ({}, {})", x, y));
+    }
+    match result.into_string().as_deref() {
+        Ok("mine") => Ok(Action::Mine),
+        Ok("unload") => Ok(Action::Unload),
+        Ok("recharge") => Ok(Action::Recharge),
+        Ok("wait") => Ok(Action::Wait),
+        Ok("explore") => Ok(behavior::explore(memory, robot, knowledge)),
+        Ok("collect") => Ok(behavior::collect(memory, robot, knowledge)),
+        Ok("return") => Ok(behavior::dock(memory, robot, knowledge).unwrap_or(Action::Wait)),
+        Ok(other) => Direction::from_name(other)
+            .map(Action::Move)
+            .ok_or_else(|| format!("action inconnue '{}'", other)),
+        Err(kind) => Err(format!("action attendue, {} obtenu", kind)),
+    }
+}
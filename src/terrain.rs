@@ -0,0 +1,45 @@
+// src/terrain.rs
+
+// Types de terrain, déduits de la valeur du bruit de Perlin
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Terrain {
+    Plain, // plaine, déplacement normal
+    Sand,  // sable, déplacement ralenti
+    Rough, // terrain accidenté, déplacement difficile
+    Rock,  // roche, infranchissable
+}
+
+impl Terrain {
+    // Fonction pour classer une valeur du bruit de Perlin par bandes
+    pub fn from_noise(value: f64) -> Self {
+        if value > 0.5 {
+            Terrain::Rock
+        } else if value > 0.3 {
+            Terrain::Rough
+        } else if value > 0.15 {
+            Terrain::Sand
+        } else {
+            Terrain::Plain
+        }
+    }
+
+    // Fonction pour obtenir le coût (en batterie) pour entrer sur la case, None si infranchissable
+    pub fn cost(self) -> Option<usize> {
+        match self {
+            Terrain::Plain => Some(1),
+            Terrain::Sand => Some(2),
+            Terrain::Rough => Some(3),
+            Terrain::Rock => None,
+        }
+    }
+
+    // Fonction pour obtenir la couleur du terrain dans draw_map
+    pub fn color(self) -> u32 {
+        match self {
+            Terrain::Plain => 0xFF_FFFFFF,
+            Terrain::Sand => 0xFF_EEDD99,
+            Terrain::Rough => 0xFF_998866,
+            Terrain::Rock => 0xFF_000000,
+        }
+    }
+}
@@ -0,0 +1,162 @@
+// src/tour.rs
+use crate::pathfinding::{find_path, path_cost};
+use crate::{Map, BATTERY_RESERVE};
+use std::collections::HashMap;
+
+// Statistiques des voyages de collecte, tenues par la base : un voyage se termine quand un
+// robot décharge une cargaison
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TripStats {
+    pub trips: usize, // voyages terminés
+    pub units: usize, // unités rapportées
+    pub spent: usize, // batterie dépensée en déplacements par les collecteurs
+}
+
+impl TripStats {
+    // Fonction pour obtenir le nombre moyen d'unités rapportées par voyage
+    pub fn units_per_trip(&self) -> f64 {
+        self.units as f64 / self.trips.max(1) as f64
+    }
+
+    // Fonction pour obtenir la batterie dépensée en moyenne par unité rapportée
+    pub fn spent_per_unit(&self) -> f64 {
+        self.spent as f64 / self.units.max(1) as f64
+    }
+}
+
+fn distance(a: (usize, usize), b: (usize, usize)) -> usize {
+    a.0.abs_diff(b.0).max(a.1.abs_diff(b.1))
+}
+
+// Fonction pour estimer la longueur d'une tournée : du départ à chaque étape dans l'ordre,
+// puis à l'arrivée
+fn length(start: (usize, usize), stops: &[(usize, usize)], end: (usize, usize)) -> usize {
+    std::iter::once(start)
+        .chain(stops.iter().copied())
+        .zip(stops.iter().copied().chain(Some(end)))
+        .map(|(a, b)| distance(a, b))
+        .sum()
+}
+
+// Fonction pour améliorer l'ordre des étapes (2-opt) : un tronçon est inversé tant que cela
+// raccourcit la tournée
+pub fn two_opt(start: (usize, usize), stops: &mut [(usize, usize)], end: (usize, usize)) {
+    let mut improved = true;
+    while improved {
+        improved = false;
+        for i in 0..stops.len() {
+            for j in i + 1..stops.len() {
+                let before = length(start, stops, end);
+                stops[i..=j].reverse();
+                if length(start, stops, end) < before {
+                    improved = true;
+                } else {
+                    stops[i..=j].reverse();
+                }
+            }
+        }
+    }
+}
+
+// Fonction pour calculer le coût du trajet connu entre deux étapes, None si l'arrivée est hors
+// d'atteinte
+fn leg_cost(map: &Map, from: (usize, usize), to: (usize, usize)) -> Option<usize> {
+    Some(path_cost(map, &find_path(map, from, to)?))
+}
+
+// Fonction pour calculer la batterie nécessaire à une tournée : les trajets connus entre les
+// étapes, puis le retour prudent à la base, dont le coût est donné par `home_cost`. None si
+// une étape ou la base est hors d'atteinte.
+pub fn tour_cost(
+    map: &Map,
+    start: (usize, usize),
+    stops: &[(usize, usize)],
+    home_cost: impl Fn((usize, usize)) -> Option<usize>,
+) -> Option<usize> {
+    let mut cost = 0;
+    let mut position = start;
+    for &stop in stops {
+        cost += leg_cost(map, position, stop)?;
+        position = stop;
+    }
+    Some(cost + home_cost(position)?)
+}
+
+// Fonction pour planifier une tournée de collecte d'au plus `stops` gisements parmi des
+// candidats : le plus proche d'abord, puis l'ordre est amélioré par 2-opt. Les dernières
+// étapes sont retirées tant que la batterie ne couvre pas la tournée et le retour, réserve
+// comprise. Une tournée vide signifie qu'aucun gisement n'est à portée.
+pub fn plan_tour(
+    map: &Map,
+    start: (usize, usize),
+    candidates: &[(usize, usize)],
+    stops: usize,
+    battery: usize,
+    home_cost: impl Fn((usize, usize)) -> Option<usize>,
+) -> Vec<(usize, usize)> {
+    let nearest = |position: (usize, usize), tour: &[(usize, usize)]| {
+        let stop = candidates
+            .iter()
+            .copied()
+            .filter(|stop| !tour.contains(stop))
+            .min_by_key(|&stop| distance(position, stop))?;
+        Some((stop, leg_cost(map, position, stop)?))
+    };
+    plan_tour_with(map, start, nearest, stops, battery, home_cost)
+}
+
+// Fonction pour planifier une tournée comme `plan_tour`, chaque étape étant donnée par
+// `nearest` : le gisement le plus proche d'une position, hors des étapes déjà retenues, avec
+// le coût du trajet pour l'atteindre. Les coûts des trajets sont cumulés en construisant la
+// tournée ; seuls ceux que 2-opt a changés sont recalculés.
+pub fn plan_tour_with(
+    map: &Map,
+    start: (usize, usize),
+    mut nearest: impl FnMut((usize, usize), &[(usize, usize)]) -> Option<((usize, usize), usize)>,
+    stops: usize,
+    battery: usize,
+    home_cost: impl Fn((usize, usize)) -> Option<usize>,
+) -> Vec<(usize, usize)> {
+    let mut tour = Vec::new();
+    let mut legs = HashMap::new();
+    let mut position = start;
+    while tour.len() < stops {
+        let Some((stop, cost)) = nearest(position, &tour) else {
+            break;
+        };
+        legs.insert((position, stop), cost);
+        tour.push(stop);
+        position = stop;
+    }
+    two_opt(start, &mut tour, map.base);
+
+    // Coût cumulé jusqu'à chaque étape ; un trajet devenu impossible après 2-opt coupe la
+    // tournée
+    let mut prefix = Vec::with_capacity(tour.len());
+    let mut cost = 0;
+    let mut position = start;
+    for &stop in &tour {
+        let leg = match legs.get(&(position, stop)) {
+            Some(&leg) => Some(leg),
+            None => leg_cost(map, position, stop),
+        };
+        let Some(leg) = leg else {
+            break;
+        };
+        cost += leg;
+        prefix.push(cost);
+        position = stop;
+    }
+
+    // Les dernières étapes sont retirées tant que la batterie ne couvre pas l'aller et le
+    // retour depuis la dernière étape gardée
+    let kept = (1..=prefix.len())
+        .rev()
+        .find(|&count| {
+            home_cost(tour[count - 1])
+                .is_some_and(|home| prefix[count - 1] + home + BATTERY_RESERVE <= battery)
+        })
+        .unwrap_or(0);
+    tour.truncate(kept);
+    tour
+}
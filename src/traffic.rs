@@ -0,0 +1,122 @@
+// src/traffic.rs
+use crate::action::{self, Action, ActionError, Direction};
+use crate::mapf::FleetPlanner;
+use crate::{Map, Robot};
+use std::collections::{BTreeMap, HashSet};
+
+// Nombre de tours passés bloqué avant qu'un robot ne cède le passage en s'écartant
+pub const YIELD_AFTER: usize = 3;
+
+// Règle de circulation entre les robots. Avec des cases exclusives, une case (hors base) ne
+// reçoit qu'un robot à la fois : chaque robot réserve la case où il se trouve et celle où il
+// se déplace pendant le tour, et un robot bloqué attend puis cède le passage. Avec un
+// planificateur de flotte, les déplacements sont planifiés ensemble et sans collision.
+#[derive(Debug, Default)]
+pub struct Traffic {
+    pub exclusive: bool,                   // un seul robot par case, sauf à la base
+    pub fleet: Option<FleetPlanner>,       // planification commune des déplacements
+    pub reserved: HashSet<(usize, usize)>, // cases occupées ou réservées pendant le tour
+    pub stats: TrafficStats,               // mesures des embouteillages
+}
+
+// Mesures des embouteillages
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TrafficStats {
+    pub waits: usize,                          // déplacements refusés faute de place
+    pub yields: usize,                         // robots qui se sont écartés pour céder le passage
+    pub jams: BTreeMap<(usize, usize), usize>, // déplacements refusés, par case visée
+}
+
+impl TrafficStats {
+    // Fonction pour compter les déplacements refusés vers les cases proches d'une position
+    pub fn jams_near(&self, (x, y): (usize, usize), radius: usize) -> usize {
+        self.jams
+            .iter()
+            .filter(|(&(jx, jy), _)| jx.abs_diff(x) <= radius && jy.abs_diff(y) <= radius)
+            .map(|(_, &count)| count)
+            .sum()
+    }
+}
+
+impl Traffic {
+    // Fonction pour créer une règle de circulation, exclusive ou libre
+    pub fn new(exclusive: bool) -> Self {
+        Traffic {
+            exclusive,
+            ..Traffic::default()
+        }
+    }
+
+    // Fonction pour créer une règle de circulation exclusive où la flotte planifie ses
+    // déplacements en commun
+    pub fn planned(window: usize) -> Self {
+        Traffic {
+            exclusive: true,
+            fleet: Some(FleetPlanner::new(window)),
+            ..Traffic::default()
+        }
+    }
+
+    // Fonction pour réserver, en début de tour, les cases occupées par les robots
+    pub fn begin_tick(&mut self, map: &Map, robots: &[Robot]) {
+        self.reserved.clear();
+        if self.exclusive {
+            self.reserved.extend(
+                robots
+                    .iter()
+                    .map(|robot| (robot.x, robot.y))
+                    .filter(|&cell| cell != map.base),
+            );
+        }
+    }
+
+    // Fonction pour vérifier qu'une case peut recevoir un robot
+    pub fn check(&self, map: &Map, cell: (usize, usize)) -> Result<(), ActionError> {
+        if self.exclusive && cell != map.base && self.reserved.contains(&cell) {
+            return Err(ActionError::Occupied(cell));
+        }
+        Ok(())
+    }
+
+    // Fonction pour réserver la case d'arrivée d'un robot et libérer celle qu'il quitte
+    pub fn moved(&mut self, map: &Map, from: (usize, usize), to: (usize, usize)) {
+        if !self.exclusive {
+            return;
+        }
+        self.reserved.remove(&from);
+        if to != map.base {
+            self.reserved.insert(to);
+        }
+    }
+
+    // Fonction pour enregistrer un déplacement refusé faute de place. Un robot bloqué depuis
+    // trop longtemps cède le passage : il s'écarte vers une case libre, en s'éloignant le moins
+    // possible de sa cible, et oublie son chemin pour en calculer un autre.
+    pub fn blocked(&mut self, map: &Map, robot: &mut Robot, cell: (usize, usize)) {
+        self.stats.waits += 1;
+        *self.stats.jams.entry(cell).or_insert(0) += 1;
+        robot.blocked += 1;
+        if robot.blocked < YIELD_AFTER {
+            return;
+        }
+        let target = robot.route.target.unwrap_or(map.base);
+        let aside = Direction::ALL
+            .into_iter()
+            .filter_map(|direction| {
+                let next = direction.step(map, (robot.x, robot.y))?;
+                (next != cell
+                    && action::validate(map, self, robot, Action::Move(direction)).is_ok())
+                .then_some((direction, next))
+            })
+            .min_by_key(|&(_, (x, y))| x.abs_diff(target.0).max(y.abs_diff(target.1)));
+        if let Some((direction, next)) = aside {
+            let from = (robot.x, robot.y);
+            if action::apply_move(map, self, robot, direction).is_ok() {
+                self.moved(map, from, next);
+                self.stats.yields += 1;
+                robot.blocked = 0;
+                robot.route.path.clear();
+            }
+        }
+    }
+}
@@ -0,0 +1,299 @@
+// src/ui.rs
+use crate::{Map, Robot, Task};
+
+// Hauteur d'une ligne de texte du panneau, en pixels
+const LINE_HEIGHT: usize = 12;
+
+// Largeur d'un caractère du panneau, espace compris, en pixels
+const CHAR_WIDTH: usize = 6;
+
+// Marge autour du texte du panneau, en pixels
+const PADDING: usize = 2;
+
+// Couleurs du fond et du texte du panneau
+const PANEL_COLOR: u32 = 0xFF_202020;
+const TEXT_COLOR: u32 = 0xFF_FFFFFF;
+
+// Elément d'interface : une ligne de texte ancrée à une position de la fenêtre
+pub struct UiElement {
+    pub label: String,            // texte affiché
+    pub position: (usize, usize), // position d'ancrage en pixels
+}
+
+impl UiElement {
+    // Fonction pour créer un nouvel élément d'interface
+    pub fn new(label: &str, position: (usize, usize)) -> Self {
+        UiElement {
+            label: label.to_string(),
+            position,
+        }
+    }
+}
+
+// Fonction pour produire le rendu texte des éléments d'interface, une ligne par élément
+pub fn render_ui(elements: &[UiElement]) -> Vec<String> {
+    elements
+        .iter()
+        .map(|element| element.label.clone())
+        .collect()
+}
+
+// Fonction pour dessiner les éléments d'interface dans une image de la fenêtre : chaque ligne
+// est écrite sur un fond sombre à sa position d'ancrage. Ce qui dépasse de l'image est coupé.
+pub fn draw_ui(buffer: &mut [u32], width: usize, elements: &[UiElement]) {
+    if width == 0 {
+        return;
+    }
+    let height = buffer.len() / width;
+    let mut put = |x: usize, y: usize, color: u32| {
+        if x < width && y < height {
+            buffer[y * width + x] = color;
+        }
+    };
+    for element in elements {
+        let (left, top) = element.position;
+        let columns = element.label.chars().count() * CHAR_WIDTH + 2 * PADDING;
+        for y in top..top + LINE_HEIGHT {
+            for x in left..left + columns {
+                put(x, y, PANEL_COLOR);
+            }
+        }
+        for (column, symbol) in element.label.chars().enumerate() {
+            let origin = (left + PADDING + column * CHAR_WIDTH, top + PADDING);
+            for (row, bits) in glyph(symbol).iter().enumerate() {
+                for dx in 0..5 {
+                    if bits & (0b10000 >> dx) != 0 {
+                        put(origin.0 + dx, origin.1 + row, TEXT_COLOR);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Fonction pour obtenir le dessin 5x7 d'un caractère, une ligne de 5 bits par rangée. Les
+// minuscules s'écrivent en majuscules et les lettres accentuées sans leur accent.
+fn glyph(symbol: char) -> [u8; 7] {
+    let symbol = match symbol.to_uppercase().next().unwrap_or(symbol) {
+        'É' | 'È' | 'Ê' | 'Ë' => 'E',
+        'À' | 'Â' | 'Ä' => 'A',
+        'Î' | 'Ï' => 'I',
+        'Ô' | 'Ö' => 'O',
+        'Ù' | 'Û' | 'Ü' => 'U',
+        'Ç' => 'C',
+        other => other,
+    };
+    match symbol {
+        'A' => [14, 17, 17, 31, 17, 17, 17],
+        'B' => [30, 17, 17, 30, 17, 17, 30],
+        'C' => [14, 17, 16, 16, 16, 17, 14],
+        'D' => [30, 17, 17, 17, 17, 17, 30],
+        'E' => [31, 16, 16, 30, 16, 16, 31],
+        'F' => [31, 16, 16, 30, 16, 16, 16],
+        'G' => [14, 17, 16, 23, 17, 17, 15],
+        'H' => [17, 17, 17, 31, 17, 17, 17],
+        'I' => [14, 4, 4, 4, 4, 4, 14],
+        'J' => [7, 2, 2, 2, 2, 18, 12],
+        'K' => [17, 18, 20, 24, 20, 18, 17],
+        'L' => [16, 16, 16, 16, 16, 16, 31],
+        'M' => [17, 27, 21, 21, 17, 17, 17],
+        'N' => [17, 17, 25, 21, 19, 17, 17],
+        'O' => [14, 17, 17, 17, 17, 17, 14],
+        'P' => [30, 17, 17, 30, 16, 16, 16],
+        'Q' => [14, 17, 17, 17, 21, 18, 13],
+        'R' => [30, 17, 17, 30, 20, 18, 17],
+        'S' => [15, 16, 16, 14, 1, 1, 30],
+        'T' => [31, 4, 4, 4, 4, 4, 4],
+        'U' => [17, 17, 17, 17, 17, 17, 14],
+        'V' => [17, 17, 17, 17, 17, 10, 4],
+        'W' => [17, 17, 17, 21, 21, 21, 10],
+        'X' => [17, 17, 10, 4, 10, 17, 17],
+        'Y' => [17, 17, 10, 4, 4, 4, 4],
+        'Z' => [31, 1, 2, 4, 8, 16, 31],
+        '0' => [14, 17, 19, 21, 25, 17, 14],
+        '1' => [4, 12, 4, 4, 4, 4, 14],
+        '2' => [14, 17, 1, 2, 4, 8, 31],
+        '3' => [31, 2, 4, 2, 1, 17, 14],
+        '4' => [2, 6, 10, 18, 31, 2, 2],
+        '5' => [31, 16, 30, 1, 1, 17, 14],
+        '6' => [6, 8, 16, 30, 17, 17, 14],
+        '7' => [31, 1, 2, 4, 8, 8, 8],
+        '8' => [14, 17, 17, 14, 17, 17, 14],
+        '9' => [14, 17, 17, 15, 1, 2, 12],
+        ' ' => [0; 7],
+        '(' => [2, 4, 8, 8, 8, 4, 2],
+        ')' => [8, 4, 2, 2, 2, 4, 8],
+        ',' => [0, 0, 0, 0, 12, 4, 8],
+        '.' => [0, 0, 0, 0, 0, 12, 12],
+        ':' => [0, 12, 12, 0, 12, 12, 0],
+        '#' => [10, 10, 31, 10, 31, 10, 10],
+        '/' => [0, 1, 2, 4, 8, 16, 0],
+        '-' => [0, 0, 0, 31, 0, 0, 0],
+        '>' => [8, 4, 2, 1, 2, 4, 8],
+        '_' => [0, 0, 0, 0, 0, 0, 31],
+        _ => [14, 17, 1, 2, 4, 0, 4],
+    }
+}
+
+// Fonction pour convertir une position de la souris (en pixels) en case de la carte
+pub fn cell_at(
+    map: &Map,
+    window_size: (usize, usize),
+    mouse: (f32, f32),
+) -> Option<(usize, usize)> {
+    let (window_width, window_height) = window_size;
+    if window_width == 0 || window_height == 0 || mouse.0 < 0.0 || mouse.1 < 0.0 {
+        return None;
+    }
+    let x = (mouse.0 as usize * map.width) / window_width;
+    let y = (mouse.1 as usize * map.height) / window_height;
+    if x < map.width && y < map.height {
+        Some((x, y))
+    } else {
+        None
+    }
+}
+
+// Fonction pour décrire une case : coordonnées, obstacle, exploration, gisements et robots présents
+pub fn inspect_cell(
+    map: &Map,
+    robots: &[Robot],
+    cell: (usize, usize),
+    anchor: (usize, usize),
+) -> Vec<UiElement> {
+    let (x, y) = cell;
+    let mut lines = vec![
+        format!("Case ({}, {})", x, y),
+        format!("Terrain : {:?}", map.terrain_at((x, y))),
+        format!("Obstacle : {}", yes_no(map.is_obstacle((x, y)))),
+        format!("Explorée : {}", yes_no(map.is_explored((x, y)))),
+    ];
+
+    let mut deposits = Vec::new();
+    if let Some(kind) = map.deposit_at(cell) {
+        deposits.push(map.resources.get(kind).name.as_str());
+    }
+    if cell == map.base {
+        deposits.push("base");
+    }
+    if deposits.is_empty() {
+        lines.push("Contenu : aucun".to_string());
+    } else {
+        lines.push(format!("Contenu : {}", deposits.join(", ")));
+    }
+    if let Some(depleted) = map.depleted.iter().find(|depleted| depleted.cell == cell) {
+        lines.push(format!(
+            "Régénération : {} dans {} tours",
+            map.resources.get(depleted.kind).name,
+            depleted.remaining
+        ));
+    }
+
+    let present: Vec<String> = robots
+        .iter()
+        .enumerate()
+        .filter(|(_, robot)| (robot.x, robot.y) == cell)
+        .map(|(index, robot)| format!("#{} {}", index, task_label(map, robot.task)))
+        .collect();
+    if present.is_empty() {
+        lines.push("Robots : aucun".to_string());
+    } else {
+        lines.push(format!("Robots : {}", present.join(", ")));
+    }
+
+    to_elements(lines, anchor)
+}
+
+// Fonction pour décrire un robot : tâche, état, chargement, cible et chemin prévu
+pub fn inspect_robot(
+    map: &Map,
+    robot: &Robot,
+    index: usize,
+    anchor: (usize, usize),
+) -> Vec<UiElement> {
+    let target = match robot.route.target {
+        Some((x, y)) => format!("({}, {})", x, y),
+        None => "aucune".to_string(),
+    };
+    let path: Vec<String> = robot
+        .planned_path()
+        .iter()
+        .map(|(x, y)| format!("({}, {})", x, y))
+        .collect();
+    let load: Vec<String> = map
+        .resources
+        .kinds()
+        .map(|kind| format!("{} {}", robot.carried(kind), map.resources.get(kind).name))
+        .collect();
+    let strategy = match (&robot.behavior, robot.task) {
+        (Some(behavior), _) => behavior.name(),
+        (None, Task::Explore) => "explorer",
+        (None, Task::Collect(_)) => "collector",
+    };
+    let lines = vec![
+        format!("Robot #{} en ({}, {})", index, robot.x, robot.y),
+        format!("Tâche : {}", task_label(map, robot.task)),
+        format!("Stratégie : {}", strategy),
+        format!("Etat : {:?}", robot.state),
+        format!("Chargement : {}", load.join(", ")),
+        format!(
+            "Batterie : {}/{}",
+            robot.battery,
+            robot.modules.battery_capacity()
+        ),
+        format!(
+            "Modules : capteur {}, foreuse {}, soute {}, batterie {}, roues {}, analyse {}",
+            robot.modules.sensor,
+            robot.modules.drill,
+            robot.modules.cargo,
+            robot.modules.battery,
+            robot.modules.wheels,
+            robot.modules.analysis
+        ),
+        format!("Cible : {}", target),
+        format!(
+            "Chemin : {}",
+            if path.is_empty() {
+                "aucun".to_string()
+            } else {
+                path.join(" -> ")
+            }
+        ),
+    ];
+
+    to_elements(lines, anchor)
+}
+
+// Fonction pour colorer une liste de cases dans une image de la carte
+pub fn highlight(buffer: &mut [u32], map: &Map, cells: &[(usize, usize)], color: u32) {
+    for &(x, y) in cells {
+        if x < map.width && y < map.height {
+            buffer[y * map.width + x] = color;
+        }
+    }
+}
+
+// Fonction pour nommer une tâche, la ressource collectée étant désignée par son mot-clé
+pub fn task_label(map: &Map, task: Task) -> String {
+    match task {
+        Task::Collect(kind) => format!("Collect({})", map.resources.get(kind).key),
+        Task::Explore => "Explore".to_string(),
+    }
+}
+
+// Fonction pour empiler des lignes de texte sous un point d'ancrage
+fn to_elements(lines: Vec<String>, anchor: (usize, usize)) -> Vec<UiElement> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(row, line)| UiElement::new(line, (anchor.0, anchor.1 + row * LINE_HEIGHT)))
+        .collect()
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        "oui"
+    } else {
+        "non"
+    }
+}
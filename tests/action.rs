@@ -0,0 +1,136 @@
+#[cfg(test)]
+mod action_tests {
+
+    use erea::action::{apply, validate, Action, ActionError, Direction};
+    use erea::ascii_map::parse_ascii_map;
+    use erea::base::Base;
+    use erea::editor::reveal;
+    use erea::resources::{ENERGY, MINERALS};
+    use erea::traffic::Traffic;
+    use erea::{Robot, Task};
+
+    #[test]
+    fn test_invalid_actions_are_rejected() {
+        let mut map = parse_ascii_map("B:#\n.E.\n").unwrap();
+        reveal(&mut map);
+        let mut base = Base::new();
+        let mut robot = Robot::new(1, 0, Task::Explore);
+
+        assert_eq!(
+            validate(&map, &base.traffic, &robot, Action::Move(Direction::North)),
+            Err(ActionError::OutOfBounds)
+        );
+        assert_eq!(
+            validate(&map, &base.traffic, &robot, Action::Move(Direction::East)),
+            Err(ActionError::Obstacle((2, 0)))
+        );
+        assert_eq!(
+            validate(&map, &base.traffic, &robot, Action::Unload),
+            Err(ActionError::NotAtBase)
+        );
+        assert_eq!(
+            validate(&map, &base.traffic, &robot, Action::Mine),
+            Err(ActionError::NoTool)
+        );
+
+        // Une action refusée ne modifie rien
+        robot.battery = 0;
+        assert_eq!(
+            apply(
+                &mut map,
+                &mut robot,
+                &mut base,
+                Action::Move(Direction::West)
+            ),
+            Err(ActionError::LowBattery {
+                needed: 1,
+                available: 0
+            })
+        );
+        assert_eq!((robot.x, robot.y), (1, 0));
+
+        let robot = Robot::new(1, 0, Task::Collect(ENERGY));
+        assert_eq!(
+            validate(&map, &base.traffic, &robot, Action::Mine),
+            Err(ActionError::NoDeposit)
+        );
+    }
+
+    #[test]
+    fn test_actions_are_applied() {
+        let mut map = parse_ascii_map("B:\n.E\n").unwrap();
+        reveal(&mut map);
+        let mut base = Base::new();
+        let mut robot = Robot::new(0, 0, Task::Collect(ENERGY));
+        let capacity = robot.battery;
+
+        // Le déplacement coûte le terrain d'arrivée, puis le robot collecte sur place
+        apply(
+            &mut map,
+            &mut robot,
+            &mut base,
+            Action::Move(Direction::SouthEast),
+        )
+        .unwrap();
+        assert_eq!((robot.x, robot.y), (1, 1));
+        assert_eq!(robot.battery, capacity - 1);
+        apply(&mut map, &mut robot, &mut base, Action::Mine).unwrap();
+        assert_eq!(robot.carried(ENERGY), 1);
+
+        // À la base, la cargaison rejoint le stock et la batterie est rechargée
+        apply(
+            &mut map,
+            &mut robot,
+            &mut base,
+            Action::Move(Direction::NorthWest),
+        )
+        .unwrap();
+        apply(&mut map, &mut robot, &mut base, Action::Unload).unwrap();
+        apply(&mut map, &mut robot, &mut base, Action::Recharge).unwrap();
+        assert_eq!(base.stock(ENERGY), 1);
+        assert_eq!(robot.cargo(), 0);
+        assert_eq!(robot.battery, capacity);
+    }
+
+    #[test]
+    fn test_occupied_cell_is_rejected() {
+        let mut map = parse_ascii_map("B..\n...\n").unwrap();
+        reveal(&mut map);
+        let mut base = Base::new();
+        base.traffic = Traffic::new(true);
+        let mut robots = vec![
+            Robot::new(1, 0, Task::Explore),
+            Robot::new(2, 0, Task::Explore),
+        ];
+        base.traffic.begin_tick(&map, &robots);
+
+        let robot = &mut robots[0];
+        assert_eq!(
+            validate(&map, &base.traffic, robot, Action::Move(Direction::East)),
+            Err(ActionError::Occupied((2, 0)))
+        );
+        assert_eq!(
+            apply(&mut map, robot, &mut base, Action::Move(Direction::East)),
+            Err(ActionError::Occupied((2, 0)))
+        );
+        assert_eq!((robot.x, robot.y), (1, 0));
+        assert!(validate(&map, &base.traffic, robot, Action::Move(Direction::South)).is_ok());
+    }
+
+    #[test]
+    fn test_recharge_does_not_upgrade() {
+        let mut map = parse_ascii_map("B.\n").unwrap();
+        reveal(&mut map);
+        let mut base = Base::new();
+        base.store(ENERGY, 50);
+        base.store(MINERALS, 50);
+        let mut robot = Robot::new(0, 0, Task::Explore);
+        let modules = robot.modules;
+        robot.battery = 1;
+
+        apply(&mut map, &mut robot, &mut base, Action::Recharge).unwrap();
+        assert_eq!(robot.battery, robot.modules.battery_capacity());
+        assert_eq!(robot.modules, modules);
+        assert_eq!((base.stock(ENERGY), base.stock(MINERALS)), (50, 50));
+    }
+}
@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod ascii_map_tests {
+
+    use erea::ascii_map::{map_to_ascii, parse_ascii_map};
+    use erea::clock::Clock;
+    use erea::map_file::load_map;
+    use erea::resources::{ENERGY, MINERALS};
+    use erea::{collect_resources, generate_map, Robot, RobotState, Task};
+
+    #[test]
+    fn test_parse_ascii_map() {
+        let map = parse_ascii_map(
+            "#####\n\
+             #E.M#\n\
+             #.B.#\n\
+             #####\n",
+        )
+        .unwrap();
+
+        assert_eq!((map.width, map.height), (5, 4));
+        assert_eq!(map.base, (2, 2));
+        assert_eq!(map.deposits(ENERGY), [(1, 1)]);
+        assert_eq!(map.deposits(MINERALS), [(3, 1)]);
+        assert!(map.is_obstacle((0, 0)));
+        assert!(!map.is_obstacle((1, 2)));
+    }
+
+    #[test]
+    fn test_invalid_ascii_map() {
+        // Lignes de largeurs différentes, caractère inconnu, base absente ou en double
+        assert!(parse_ascii_map("..B\n..\n").is_err());
+        assert!(parse_ascii_map("..B\n.?.\n").is_err());
+        assert!(parse_ascii_map("...\n...\n").is_err());
+        assert!(parse_ascii_map("B.B\n...\n").is_err());
+    }
+
+    #[test]
+    fn test_generated_map_round_trip() {
+        let map = generate_map(30, 20);
+        let text = map_to_ascii(&map);
+        let loaded = parse_ascii_map(&text).unwrap();
+
+        assert_eq!(map_to_ascii(&loaded), text);
+        assert_eq!(loaded.base, map.base);
+    }
+
+    #[test]
+    fn test_collector_on_ascii_map() {
+        let mut map = parse_ascii_map("B..E\n").unwrap();
+        let mut robot = Robot::new(0, 0, Task::Collect(ENERGY));
+        robot.state = RobotState::Collecting;
+
+        for _ in 0..4 {
+            collect_resources(&mut robot, &mut map, &Clock::default());
+        }
+
+        assert_eq!(robot.carried(ENERGY), 1);
+        assert!(map.deposits(ENERGY).is_empty());
+    }
+
+    #[test]
+    fn test_load_scenario_map() {
+        let map = load_map("maps/corridor.txt").unwrap();
+        assert_eq!((map.width, map.height), (15, 9));
+        assert_eq!(map.base, (6, 4));
+        assert_eq!(map.deposits(ENERGY).len(), 2);
+        assert_eq!(map.deposits(MINERALS).len(), 2);
+    }
+}
@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod auction_tests {
+
+    use erea::ascii_map::parse_ascii_map;
+    use erea::auction::{frontiers, offers, run_auction, AuctionPolicy, Offer, FRONTIER_SPACING};
+    use erea::base::Base;
+    use erea::clock::Clock;
+    use erea::editor::reveal;
+    use erea::frontier::FrontierIndex;
+    use erea::modules::Modules;
+    use erea::resources::ENERGY;
+    use erea::scheduler::{policy_from_name, schedule, Policy, Scheduler};
+    use erea::{empty_map, reveal_around, Robot, RobotState, Task};
+
+    #[test]
+    fn test_closest_robot_wins_deposit() {
+        let mut map = empty_map(10, 10);
+        map.add_deposit(ENERGY, (8, 5));
+        reveal(&mut map);
+        let mut robots = vec![
+            Robot::new(7, 5, Task::Collect(ENERGY)),
+            Robot::new(5, 5, Task::Explore),
+        ];
+        let base = Base::new();
+
+        // Le robot le plus proche remporte le gisement, l'autre garde sa tâche
+        let winners = run_auction(&map, &robots, &base);
+        assert_eq!(winners.len(), 1);
+        assert_eq!(winners[0].robot, 0);
+        assert_eq!(winners[0].offer, Offer::Deposit(ENERGY, (8, 5)));
+
+        // Sans assez de batterie pour l'aller-retour, il ne se porte pas candidat
+        robots[0].battery = 4;
+        let tasks = AuctionPolicy.assign(&map, &robots, &base);
+        assert_eq!(tasks, [Task::Collect(ENERGY), Task::Collect(ENERGY)]);
+
+        // Un robot à la soute pleine garde sa livraison
+        let capacity = robots[1].modules.cargo_capacity();
+        robots[1].load.insert(ENERGY, capacity);
+        let tasks = AuctionPolicy.assign(&map, &robots, &base);
+        assert_eq!(tasks, [Task::Collect(ENERGY), Task::Explore]);
+    }
+
+    #[test]
+    fn test_frontiers_are_spread_out() {
+        let mut map = empty_map(20, 20);
+        let base = map.base;
+        reveal_around(&mut map, base, 2);
+        let robots: Vec<Robot> = (0..3)
+            .map(|_| Robot::new(10, 10, Task::Collect(ENERGY)))
+            .collect();
+
+        let cells = frontiers(&map, &FrontierIndex::new(&map), robots.len());
+        assert_eq!(cells.len(), 3);
+        for (i, &a) in cells.iter().enumerate() {
+            assert!(!map.is_explored(a));
+            for &b in &cells[i + 1..] {
+                assert!(a.0.abs_diff(b.0).max(a.1.abs_diff(b.1)) >= FRONTIER_SPACING);
+            }
+        }
+
+        // Aucun gisement connu : toute la flotte part explorer
+        let policy = policy_from_name("auction").unwrap();
+        assert_eq!(policy.name(), "auction");
+        assert_eq!(
+            policy.assign(&map, &robots, &Base::new()),
+            [Task::Explore; 3]
+        );
+    }
+
+    #[test]
+    fn test_winners_head_for_their_lot() {
+        let mut map = empty_map(20, 20);
+        let home = map.base;
+        reveal_around(&mut map, home, 5);
+        map.add_deposit(ENERGY, (home.0 + 2, home.1));
+        let mut robots = vec![
+            Robot::new(home.0, home.1, Task::Collect(ENERGY)),
+            Robot::with_modules(home.0, home.1, Task::Explore, Modules::scout()),
+            Robot::new(home.0 + 1, home.1 + 1, Task::Collect(ENERGY)),
+        ];
+        let capacity = robots[2].modules.cargo_capacity();
+        robots[2].load.insert(ENERGY, capacity);
+        let mut base = Base::new();
+        base.scheduler = Scheduler::new(Box::new(AuctionPolicy), 1);
+
+        // Le gisement est réservé à son gagnant et ouvre sa tournée, l'éclaireur vise sa
+        // frontière et le robot plein rentre livrer
+        schedule(&map, &mut robots, &mut base, &Clock::default());
+        assert_eq!(robots[0].tour, [(home.0 + 2, home.1)]);
+        assert_eq!(base.claims.owner((home.0 + 2, home.1)), Some(0));
+        let goal = robots[1].goal.unwrap();
+        assert!(!map.is_explored(goal));
+        assert_eq!(robots[2].state, RobotState::Returning);
+
+        // Un gisement revendiqué par un autre robot n'est plus proposé
+        let winners = run_auction(&map, &robots[1..], &base);
+        assert!(winners
+            .iter()
+            .all(|winner| !matches!(winner.offer, Offer::Deposit(..))));
+    }
+
+    #[test]
+    fn test_bids_follow_paths_and_lots_are_capped() {
+        let mut map = parse_ascii_map(
+            "B....\n\
+             ####.\n\
+             E.E.E\n",
+        )
+        .unwrap();
+        reveal(&mut map);
+        let mut robots = vec![Robot::new(0, 0, Task::Explore)];
+        let base = Base::new();
+
+        // Un lot par robot et par ressource : seul le gisement le plus proche de la base est
+        // proposé
+        let lots: Vec<Offer> = offers(&map, &robots, &base)
+            .into_iter()
+            .filter(|offer| matches!(offer, Offer::Deposit(..)))
+            .collect();
+        assert_eq!(lots, [Offer::Deposit(ENERGY, (0, 2))]);
+
+        // Le mur impose un détour de 8 cases à l'aller comme au retour : 12 de batterie ne
+        // suffisent pas, même si le gisement est à 2 cases à vol d'oiseau
+        robots[0].battery = 12;
+        assert!(run_auction(&map, &robots, &base).is_empty());
+        robots[0].battery = 19;
+        let winners = run_auction(&map, &robots, &base);
+        assert_eq!(winners.len(), 1);
+        assert_eq!(winners[0].offer, Offer::Deposit(ENERGY, (0, 2)));
+    }
+}
@@ -0,0 +1,134 @@
+#[cfg(test)]
+mod behavior_tests {
+
+    use erea::action::{Action, Direction};
+    use erea::ascii_map::parse_ascii_map;
+    use erea::base::Base;
+    use erea::behavior::{Behavior, Collector, Knowledge, RobotView};
+    use erea::clock::Clock;
+    use erea::editor::reveal;
+    use erea::resources::ENERGY;
+    use erea::{simulate_tick, Robot, RobotState, Task};
+
+    // Stratégie de test : aller tout droit vers l'est
+    struct GoEast;
+
+    impl Behavior for GoEast {
+        fn name(&self) -> &str {
+            "go-east"
+        }
+
+        fn decide(&mut self, _robot: &RobotView, _knowledge: &Knowledge) -> Action {
+            Action::Move(Direction::East)
+        }
+    }
+
+    #[test]
+    fn test_collector_decisions() {
+        let mut map = parse_ascii_map("B..E\n").unwrap();
+        reveal(&mut map);
+        let clock = Clock::default();
+        let mut robot = Robot::new(0, 0, Task::Collect(ENERGY));
+
+        let mut collector = Collector::default();
+
+        let view = RobotView::of(&robot, &map, 1);
+        assert_eq!(
+            collector.decide(&view, &Knowledge::new(&map, &clock)),
+            Action::Move(Direction::East)
+        );
+
+        robot.x = 3;
+        let view = RobotView::of(&robot, &map, 1);
+        assert_eq!(
+            collector.decide(&view, &Knowledge::new(&map, &clock)),
+            Action::Mine
+        );
+
+        // Soute pleine : retour vers la base
+        robot.load.insert(ENERGY, robot.modules.cargo_capacity());
+        let view = RobotView::of(&robot, &map, 1);
+        assert_eq!(
+            collector.decide(&view, &Knowledge::new(&map, &clock)),
+            Action::Move(Direction::West)
+        );
+        assert_eq!(collector.memory.state, RobotState::Returning);
+    }
+
+    #[test]
+    fn test_state_follows_what_the_collector_does() {
+        let mut map = parse_ascii_map("B..E\n").unwrap();
+        reveal(&mut map);
+        let clock = Clock::default();
+        let robot = Robot::new(1, 0, Task::Collect(ENERGY));
+        let mut collector = Collector::default();
+        collector.memory.state = RobotState::Collecting;
+
+        // Le robot se rend au gisement : il reste en collecte
+        let view = RobotView::of(&robot, &map, 1);
+        assert_eq!(
+            collector.decide(&view, &Knowledge::new(&map, &clock)),
+            Action::Move(Direction::East)
+        );
+        assert_eq!(collector.memory.state, RobotState::Collecting);
+
+        // Plus rien à collecter : il rentre et se dit sur le retour
+        map.remove_deposit(ENERGY, (3, 0));
+        assert_eq!(
+            collector.decide(&view, &Knowledge::new(&map, &clock)),
+            Action::Move(Direction::West)
+        );
+        assert_eq!(collector.memory.state, RobotState::Returning);
+    }
+
+    #[test]
+    fn test_rejected_mine_keeps_tour_stop() {
+        let mut map = parse_ascii_map("BE.E\n").unwrap();
+        reveal(&mut map);
+        let clock = Clock::default();
+        let mut robot = Robot::new(1, 0, Task::Collect(ENERGY));
+        robot.modules.cargo = 2;
+        let mut collector = Collector::default();
+        collector.memory.state = RobotState::Collecting;
+
+        let view = RobotView::of(&robot, &map, 1);
+        assert_eq!(
+            collector.decide(&view, &Knowledge::new(&map, &clock)),
+            Action::Mine
+        );
+        assert_eq!(collector.memory.tour, vec![(1, 0), (3, 0)]);
+
+        // Le forage n'a pas eu lieu : le robot reprend le même gisement
+        assert_eq!(
+            collector.decide(&view, &Knowledge::new(&map, &clock)),
+            Action::Mine
+        );
+
+        // Une fois le gisement épuisé, il part vers l'étape suivante
+        map.deplete(ENERGY, (1, 0));
+        robot.load.insert(ENERGY, 1);
+        let view = RobotView::of(&robot, &map, 1);
+        assert_eq!(
+            collector.decide(&view, &Knowledge::new(&map, &clock)),
+            Action::Move(Direction::East)
+        );
+        assert_eq!(collector.memory.tour, vec![(3, 0)]);
+    }
+
+    #[test]
+    fn test_custom_behavior_drives_robot() {
+        let mut map = parse_ascii_map("B....\n").unwrap();
+        reveal(&mut map);
+        let mut robot = Robot::new(0, 0, Task::Explore);
+        robot.behavior = Some(Box::new(GoEast));
+        let mut robots = vec![robot];
+        let mut base = Base::new();
+        let mut clock = Clock::default();
+
+        for _ in 0..2 {
+            simulate_tick(&mut map, &mut robots, &mut base, &mut clock);
+        }
+        assert_eq!((robots[0].x, robots[0].y), (2, 0));
+        assert_eq!(robots[0].behavior.as_ref().unwrap().name(), "go-east");
+    }
+}
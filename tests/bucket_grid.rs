@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod bucket_grid_tests {
+
+    use erea::bucket_grid::{chebyshev, manhattan, BucketGrid};
+
+    #[test]
+    fn test_removal_moves_the_last_cell_of_the_bucket() {
+        let mut grid = BucketGrid::new(40, 20);
+        for cell in [(1, 1), (2, 1), (3, 1), (30, 10)] {
+            grid.insert(cell);
+        }
+        grid.insert((2, 1));
+        assert_eq!(grid.len(), 4);
+
+        // (3, 1) prend la place de (1, 1) dans son paquet et reste retirable
+        grid.remove((1, 1));
+        grid.remove((1, 1));
+        assert!(!grid.contains((1, 1)));
+        grid.remove((3, 1));
+        let mut cells: Vec<(usize, usize)> = grid.cells().collect();
+        cells.sort();
+        assert_eq!(cells, [(2, 1), (30, 10)]);
+        assert_eq!(grid.len(), 2);
+    }
+
+    #[test]
+    fn test_ring_search_matches_scan() {
+        let mut grid = BucketGrid::new(70, 45);
+        let cells: Vec<(usize, usize)> = (0..120).map(|i| (i * 37 % 70, i * 13 % 45)).collect();
+        for &cell in &cells {
+            grid.insert(cell);
+        }
+        for from in [(0, 0), (69, 44), (35, 20), (12, 40)] {
+            for distance in [manhattan, chebyshev] {
+                let scan = cells
+                    .iter()
+                    .copied()
+                    .filter(|cell| cell.0 % 2 == 0)
+                    .min_by_key(|&cell| (distance(from, cell), cell.1, cell.0));
+                assert_eq!(grid.nearest(from, distance, |cell| cell.0 % 2 == 0), scan);
+            }
+        }
+    }
+}
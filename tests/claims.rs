@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod claims_tests {
+
+    use erea::ascii_map::parse_ascii_map;
+    use erea::base::Base;
+    use erea::claims::{Claims, CLAIM_DURATION};
+    use erea::clock::Clock;
+    use erea::editor::reveal;
+    use erea::resources::ENERGY;
+    use erea::modules::Modules;
+    use erea::scheduler::{direct, FixedPolicy, Scheduler};
+    use erea::{simulate_tick, Robot, RobotState, Task};
+
+    #[test]
+    fn test_collectors_split_deposits() {
+        let mut map = parse_ascii_map("E.B..E\n").unwrap();
+        reveal(&mut map);
+        let mut robots = Vec::new();
+        for _ in 0..2 {
+            let mut robot = Robot::new(2, 0, Task::Collect(ENERGY));
+            robot.state = RobotState::Collecting;
+            robots.push(robot);
+        }
+        let mut base = Base::new();
+        base.scheduler = Scheduler::new(Box::new(FixedPolicy), 1);
+        let mut clock = Clock::default();
+
+        // Le second collecteur laisse le gisement le plus proche au premier
+        simulate_tick(&mut map, &mut robots, &mut base, &mut clock);
+        assert_eq!(base.claims.owner((0, 0)), Some(0));
+        assert_eq!(base.claims.owner((5, 0)), Some(1));
+        assert_eq!((robots[0].x, robots[1].x), (1, 3));
+    }
+
+    #[test]
+    fn test_claims_expire() {
+        let mut map = parse_ascii_map("BEE\n").unwrap();
+        let mut claims = Claims::default();
+
+        assert!(claims.claim((1, 0), 0, 0));
+        assert!(!claims.claim((1, 0), 1, 0));
+        assert_eq!(claims.held_by_others(1), [(1, 0)]);
+
+        // Un robot ne garde qu'une revendication
+        assert!(claims.claim((2, 0), 0, 5));
+        assert_eq!(claims.owner((1, 0)), None);
+
+        // Sans renouvellement, la revendication tombe ; un gisement épuisé est libéré
+        claims.expire(&map, 5 + CLAIM_DURATION - 1);
+        assert_eq!(claims.owner((2, 0)), Some(0));
+        claims.expire(&map, 5 + CLAIM_DURATION);
+        assert_eq!(claims.owner((2, 0)), None);
+        claims.claim((2, 0), 0, 0);
+        map.deplete(ENERGY, (2, 0));
+        claims.expire(&map, 1);
+        assert!(claims.claims.is_empty());
+    }
+
+    #[test]
+    fn test_whole_tour_is_claimed() {
+        let mut map = parse_ascii_map("B.E.E....E\n").unwrap();
+        reveal(&mut map);
+        let mut robots = Vec::new();
+        for _ in 0..2 {
+            let mut robot = Robot::with_modules(0, 0, Task::Collect(ENERGY), Modules::miner());
+            robot.state = RobotState::Collecting;
+            robots.push(robot);
+        }
+        let mut base = Base::new();
+        base.scheduler = Scheduler::new(Box::new(FixedPolicy), 1);
+        let mut clock = Clock::default();
+
+        // Les deux étapes de la tournée du premier collecteur lui sont réservées
+        simulate_tick(&mut map, &mut robots, &mut base, &mut clock);
+        assert_eq!(robots[0].tour, [(2, 0), (4, 0)]);
+        assert_eq!(base.claims.owner((2, 0)), Some(0));
+        assert_eq!(base.claims.owner((4, 0)), Some(0));
+        assert_eq!(robots[1].tour, [(9, 0)]);
+        assert_eq!(base.claims.owner((9, 0)), Some(1));
+
+        // Un robot ne garde que les gisements libres de la liste
+        let mut claims = Claims::default();
+        assert_eq!(claims.claim_all([(2, 0), (4, 0)], 0, 0), 2);
+        assert_eq!(claims.claim_all([(4, 0), (9, 0)], 1, 0), 1);
+        assert_eq!(claims.held_by_others(1), [(2, 0), (4, 0)]);
+    }
+
+    #[test]
+    fn test_directed_robot_keeps_its_later_stops() {
+        let mut map = parse_ascii_map("B.E.E.E..E\n").unwrap();
+        reveal(&mut map);
+        let mut robot = Robot::with_modules(0, 0, Task::Collect(ENERGY), Modules::miner());
+        robot.tour = vec![(2, 0), (4, 0), (6, 0)];
+        let mut base = Base::new();
+        let clock = Clock::default();
+        base.claims.claim_all(robot.tour.iter().copied(), 0, 0);
+
+        // Le gisement attribué passe en tête ; les étapes suivantes restent revendiquées
+        direct(&map, &mut robot, 0, (9, 0), &mut base, &clock);
+        assert_eq!(robot.tour, [(9, 0), (2, 0), (4, 0)]);
+        for stop in [(9, 0), (2, 0), (4, 0)] {
+            assert_eq!(base.claims.owner(stop), Some(0));
+        }
+        assert_eq!(base.claims.owner((6, 0)), None);
+
+        // Une étape déjà prévue remonte en tête sans rien perdre
+        direct(&map, &mut robot, 0, (4, 0), &mut base, &clock);
+        assert_eq!(robot.tour, [(4, 0), (9, 0), (2, 0)]);
+        assert_eq!(base.claims.held_by_others(1).len(), 3);
+    }
+}
@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod clock_tests {
+
+    use erea::ascii_map::parse_ascii_map;
+    use erea::clock::{shade, Clock, NIGHT_LIGHT, SOLAR_PERIOD};
+    use erea::{render_map, render_scene};
+
+    #[test]
+    fn test_day_and_night() {
+        let mut clock = Clock::new(100);
+        assert!(clock.is_day());
+        assert_eq!(clock.sensor_range(2), 2);
+
+        for _ in 0..60 {
+            clock.advance();
+        }
+        assert!(!clock.is_day());
+        assert_eq!(clock.light(), NIGHT_LIGHT);
+        assert_eq!(clock.sensor_range(2), 1);
+        assert_eq!(clock.solar_charge(), 0);
+
+        // Plein jour au milieu de la journée suivante
+        for _ in 0..65 {
+            clock.advance();
+        }
+        assert_eq!(clock.day(), 2);
+        assert_eq!(clock.light(), 1.0);
+    }
+
+    #[test]
+    fn test_scene_is_dimmed_at_night() {
+        let map = parse_ascii_map("B..\n...\n").unwrap();
+        let mut clock = Clock::new(10);
+        clock.tick = 2;
+        assert_eq!(render_scene(&map, &[], &clock), render_map(&map, &[]));
+
+        // Une case inexplorée (gris 0xAA) est assombrie la nuit, l'opacité est conservée
+        clock.tick = 8;
+        let night = render_scene(&map, &[], &clock);
+        let grey = (0xAA as f64 * NIGHT_LIGHT) as u32;
+        assert_eq!(night[5], 0xFF00_0000 | grey << 16 | grey << 8 | grey);
+
+        let mut buffer = vec![0xFF_FFFFFF];
+        shade(&mut buffer, 1.0);
+        assert_eq!(buffer, vec![0xFF_FFFFFF]);
+    }
+
+    #[test]
+    fn test_solar_recharge_only_by_day() {
+        let mut clock = Clock::new(40);
+        let mut day = 0;
+        let mut night = 0;
+        for _ in 0..40 {
+            if clock.is_day() {
+                day += clock.solar_charge();
+            } else {
+                night += clock.solar_charge();
+            }
+            clock.advance();
+        }
+        assert_eq!(day, 20 / SOLAR_PERIOD);
+        assert_eq!(night, 0);
+    }
+}
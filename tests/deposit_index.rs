@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod deposit_index_tests {
+
+    use erea::ascii_map::parse_ascii_map;
+    use erea::bucket_grid::manhattan;
+    use erea::pathfinding::{find_path, path_cost};
+    use erea::resources::{ResourceRegistry, ENERGY, MINERALS};
+    use erea::{generate_map_seeded, regenerate_deposits, Map};
+
+    // Gisement le plus proche en parcourant toute la liste, le premier dans l'ordre des lignes
+    // à égalité
+    fn linear_nearest(map: &Map, from: (usize, usize)) -> Option<(usize, usize)> {
+        map.deposits(ENERGY)
+            .iter()
+            .copied()
+            .filter(|&pos| pos.0 % 3 != 0)
+            .min_by_key(|&pos| (manhattan(from, pos), pos.1, pos.0))
+    }
+
+    #[test]
+    fn test_index_matches_linear_scan() {
+        let mut map = generate_map_seeded(70, 50, ResourceRegistry::standard(), 5);
+        let positions = [(0, 0), (35, 25), (69, 49), (10, 40), (60, 3), map.base];
+        let check = |map: &Map| {
+            assert_eq!(map.deposit_index(ENERGY).len(), map.deposits(ENERGY).len());
+            for from in positions {
+                let nearest = map.nearest_deposit(ENERGY, from, |pos| pos.0 % 3 != 0);
+                assert_eq!(nearest, linear_nearest(map, from));
+            }
+        };
+        check(&map);
+
+        // Les gisements épuisés disparaissent de l'index, puis reviennent en fin de liste
+        let collected: Vec<(usize, usize)> =
+            map.deposits(ENERGY).iter().step_by(2).copied().collect();
+        assert!(!collected.is_empty());
+        for &cell in &collected {
+            map.deplete(ENERGY, cell);
+        }
+        check(&map);
+        for _ in 0..200 {
+            regenerate_deposits(&mut map, true);
+        }
+        assert!(collected
+            .iter()
+            .all(|&cell| map.deposit_index(ENERGY).contains(cell)));
+        check(&map);
+    }
+
+    #[test]
+    fn test_nearest_by_path_goes_around_walls() {
+        let mut map = parse_ascii_map(
+            "..#.......\n\
+             .E#.......\n\
+             ###.......\n\
+             ..B......E\n",
+        )
+        .unwrap();
+        map.explore_all();
+
+        // Le gisement derrière le mur est le plus proche à vol d'oiseau, pas par le chemin
+        assert_eq!(
+            map.nearest_deposit(ENERGY, map.base, |_| true),
+            Some((1, 1))
+        );
+        let (cell, cost) = map
+            .nearest_deposit_by_path(ENERGY, map.base, Map::known_cost, usize::MAX, |_| true)
+            .unwrap();
+        assert_eq!(cell, (9, 3));
+
+        // Le coût est celui du meilleur chemin A* parmi tous les gisements
+        let best = map
+            .deposits(ENERGY)
+            .iter()
+            .filter_map(|&pos| find_path(&map, map.base, pos))
+            .map(|path| path_cost(&map, &path))
+            .min();
+        assert_eq!(Some(cost), best);
+
+        // Sans gisement accepté, il n'y a rien à chercher
+        assert_eq!(
+            map.nearest_deposit_by_path(MINERALS, map.base, Map::known_cost, usize::MAX, |_| true),
+            None
+        );
+    }
+
+    #[test]
+    fn test_removals_keep_list_and_index_in_step() {
+        let mut map = generate_map_seeded(40, 40, ResourceRegistry::standard(), 8);
+        let cells: Vec<(usize, usize)> = map.deposits(ENERGY).to_vec();
+        assert!(cells.len() > 4);
+
+        // Chaque gisement retiré laisse sa place au dernier de la liste ; la liste, l'index et
+        // les cases restent d'accord
+        for (i, &cell) in cells.iter().enumerate() {
+            if i % 2 == 0 {
+                map.remove_deposit(ENERGY, cell);
+            } else {
+                map.clear_deposits(cell);
+                map.add_deposit(ENERGY, cell);
+            }
+            let listed = map.deposits(ENERGY);
+            assert_eq!(map.deposit_index(ENERGY).len(), listed.len());
+            assert!(listed
+                .iter()
+                .all(|&pos| map.deposit_at(pos) == Some(ENERGY)));
+            assert!(listed
+                .iter()
+                .all(|&pos| map.deposit_index(ENERGY).contains(pos)));
+        }
+        assert_eq!(map.deposits(ENERGY).len(), cells.len() / 2);
+        assert!(!map.deposit_index(ENERGY).contains(cells[0]));
+    }
+}
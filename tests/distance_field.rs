@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod distance_field_tests {
+
+    use erea::ascii_map::parse_ascii_map;
+    use erea::distance_field::DistanceField;
+    use erea::pathfinding::{find_safe_path, path_cost_with, Route};
+    use erea::base::Base;
+    use erea::resources::ResourceRegistry;
+    use erea::terrain::Terrain;
+    use erea::{generate_map_seeded, reveal_around, Map, Robot, Task};
+
+    #[test]
+    fn test_field_follows_discoveries() {
+        let mut map = generate_map_seeded(25, 25, ResourceRegistry::standard(), 3);
+        let mut home = DistanceField::new(&map);
+
+        // Chaque découverte est réparée sur place : le champ reste celui d'un recalcul complet
+        // et donne le coût du chemin prudent trouvé par A*
+        for (i, &position) in [(12, 12), (16, 12), (20, 8), (8, 18), (3, 3)]
+            .iter()
+            .enumerate()
+        {
+            reveal_around(&mut map, position, 3);
+            home.update_around(&map, position, 3);
+            let fresh = DistanceField::new(&map);
+            for y in 0..map.height {
+                for x in 0..map.width {
+                    assert_eq!(home.distance((x, y)), fresh.distance((x, y)));
+                }
+            }
+            for y in (0..map.height).step_by(4 + i) {
+                for x in (0..map.width).step_by(3) {
+                    let expected = find_safe_path(&map, (x, y), map.base)
+                        .map(|path| path_cost_with(&map, &path, Map::safe_cost));
+                    assert_eq!(home.distance((x, y)), expected);
+                }
+            }
+        }
+        assert!(home.repairs > 0);
+    }
+
+    #[test]
+    fn test_homing_and_battery() {
+        let mut map = parse_ascii_map(
+            "B....\n\
+             ###..\n\
+             .....\n",
+        )
+        .unwrap();
+        map.explore_all();
+        let home = DistanceField::new(&map);
+        assert_eq!(home.distance((0, 2)), Some(6));
+        assert_eq!(home.distance((1, 1)), None);
+
+        // Le robot descend le champ en contournant la roche
+        let mut route = Route::default();
+        let path = home.path_home(&map, (0, 2));
+        assert_eq!(path.len(), 6);
+        assert_eq!(path.last(), Some(&map.base));
+        assert!(home.next_step(&mut route, &map, (0, 2)).is_some());
+        assert_eq!(route.path, path);
+
+        let mut robot = Robot::new(0, 2, Task::Explore);
+        robot.battery = 9;
+        assert!(home.needs_recharge(&robot));
+        assert_eq!(home.needs_recharge(&robot), robot.needs_recharge(&map));
+        robot.battery = 10;
+        assert!(!home.needs_recharge(&robot));
+    }
+
+    #[test]
+    fn test_raised_costs_are_repaired_locally() {
+        let mut map = generate_map_seeded(30, 30, ResourceRegistry::standard(), 5);
+        map.explore_all();
+        let mut base = Base::new();
+        base.home.rebuild(&map);
+
+        // Des cases passent en roche, en terrain accidenté puis en plaine : le champ réparé
+        // reste celui d'un recalcul complet, sans jamais tout recalculer
+        let terrains = [Terrain::Rock, Terrain::Rough, Terrain::Plain, Terrain::Rock];
+        let mut seed = 7usize;
+        for step in 0..120 {
+            seed = (seed * 1103515245 + 12345) % (1 << 31);
+            let cell = (seed % map.width, (seed / map.width) % map.height);
+            if cell == map.base {
+                continue;
+            }
+            map.set_terrain(cell, terrains[step % terrains.len()]);
+            base.edited(&mut map, &[cell]);
+            let fresh = DistanceField::new(&map);
+            for y in 0..map.height {
+                for x in 0..map.width {
+                    assert_eq!(base.home.distance((x, y)), fresh.distance((x, y)));
+                }
+            }
+        }
+        assert_eq!(base.home.rebuilds, 1);
+        assert!(base.home.repairs > 0);
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod dstar_tests {
+
+    use erea::ascii_map::parse_ascii_map;
+    use erea::dstar::DStarLite;
+    use erea::pathfinding::{find_path, path_cost, Route};
+    use erea::resources::ResourceRegistry;
+    use erea::{generate_map_seeded, reveal_around, Map};
+
+    #[test]
+    fn test_search_matches_astar() {
+        let mut map = generate_map_seeded(25, 25, ResourceRegistry::standard(), 5);
+        let base = map.base;
+        reveal_around(&mut map, base, 6);
+        for goal in [(0, 0), (24, 3), (20, 20), (2, 22), (14, 9)] {
+            let mut search = DStarLite::new(&map, map.base, goal, Map::known_cost);
+            search.compute(&map, Map::known_cost);
+            let path = search.path(&map);
+            let expected = find_path(&map, map.base, goal).map(|path| path_cost(&map, &path));
+            assert_eq!(
+                Some(path_cost(&map, &path)).filter(|_| !path.is_empty()),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_route_is_repaired_around_new_wall() {
+        let mut map = parse_ascii_map(
+            "...#...\n\
+             ...#...\n\
+             ......B\n",
+        )
+        .unwrap();
+        let mut route = Route::default();
+        let (mut position, target) = ((0, 0), (6, 0));
+
+        // Le robot découvre le mur en chemin : la recherche est réparée, pas refaite
+        while position != target {
+            reveal_around(&mut map, position, 1);
+            let direction = route
+                .next_step(&map, position, target, Map::known_cost)
+                .unwrap();
+            let expected = find_path(&map, position, target).unwrap();
+            assert_eq!(path_cost(&map, &route.path), path_cost(&map, &expected));
+            position = direction.step(&map, position).unwrap();
+            assert!(!map.is_obstacle(position));
+        }
+        assert_eq!(route.stats.searches, 1);
+        assert!(route.stats.repairs >= 1);
+        assert!(route.stats.expansions > 0);
+    }
+
+    #[test]
+    fn test_search_only_visits_cells_it_needs() {
+        let text = format!(
+            "{}{}B\n",
+            format!("{}\n", ".".repeat(200)).repeat(199),
+            ".".repeat(199)
+        );
+        let map = parse_ascii_map(&text).unwrap();
+        let (start, goal) = ((20, 30), (26, 33));
+
+        // Une cible proche ne coûte que quelques cases, pas toute la carte
+        let mut search = DStarLite::new(&map, start, goal, Map::known_cost);
+        search.compute(&map, Map::known_cost);
+        assert_eq!(search.path(&map).last(), Some(&goal));
+        assert_eq!(search.path(&map).len(), 6);
+        assert!(
+            search.visited() < 500,
+            "{} cases examinées",
+            search.visited()
+        );
+    }
+}
@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod editor_tests {
+
+    use erea::editor::{erase, paint, Tool};
+    use erea::{empty_map, Map};
+    use erea::map_file::{map_from_str, map_to_string};
+    use erea::resources::{ENERGY, MINERALS};
+
+    #[test]
+    fn test_paint_and_erase() {
+        let mut map = empty_map(10, 10);
+
+        // Poser un obstacle puis un gisement sur la même case remplace l'obstacle
+        paint(&mut map, Tool::Obstacle, (2, 2));
+        assert!(map.is_obstacle((2, 2)));
+        paint(&mut map, Tool::Deposit(ENERGY), (2, 2));
+        assert!(!map.is_obstacle((2, 2)));
+        assert_eq!(map.deposits(ENERGY), [(2, 2)]);
+
+        erase(&mut map, Tool::Deposit(ENERGY), (2, 2));
+        assert!(map.deposits(ENERGY).is_empty());
+
+        // La base est déplacée, pas dupliquée
+        paint(&mut map, Tool::Base, (1, 8));
+        assert_eq!(map.base, (1, 8));
+    }
+
+    #[test]
+    fn test_map_file_round_trip() {
+        let mut map = empty_map(8, 6);
+        paint(&mut map, Tool::Obstacle, (0, 0));
+        paint(&mut map, Tool::Deposit(ENERGY), (3, 4));
+        paint(&mut map, Tool::Deposit(MINERALS), (7, 5));
+        paint(&mut map, Tool::Base, (2, 1));
+
+        let loaded = map_from_str(&map_to_string(&map)).unwrap();
+        assert_eq!((loaded.width, loaded.height), (8, 6));
+        let terrain = |map: &Map| map.cells().iter().map(|cell| cell.terrain).collect::<Vec<_>>();
+        assert_eq!(terrain(&loaded), terrain(&map));
+        assert_eq!(loaded.deposits(ENERGY), [(3, 4)]);
+        assert_eq!(loaded.deposits(MINERALS), [(7, 5)]);
+        assert_eq!(loaded.base, (2, 1));
+
+        // Une position hors de la carte est refusée
+        assert!(map_from_str("size 4 4\nenergy 9 9\n").is_err());
+        assert!(map_from_str("energy 1 1\n").is_err());
+    }
+}
@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod export_tests {
+
+    use erea::export::{crc32, encode_png, encode_ppm, Frame, GifRecorder};
+
+    #[test]
+    fn test_frame_scaling() {
+        let frame = Frame::new(2, 1, vec![0xFF_FF0000, 0xFF_00FF00]);
+        let scaled = frame.scaled(2);
+        assert_eq!((scaled.width, scaled.height), (4, 2));
+        assert_eq!(
+            scaled.pixels,
+            [0xFF_FF0000, 0xFF_FF0000, 0xFF_00FF00, 0xFF_00FF00].repeat(2)
+        );
+    }
+
+    #[test]
+    fn test_ppm_and_png() {
+        let frame = Frame::new(
+            2,
+            2,
+            vec![0xFF_FF0000, 0xFF_00FF00, 0xFF_0000FF, 0xFF_FFFFFF],
+        );
+
+        let ppm = encode_ppm(&frame);
+        assert!(ppm.starts_with(b"P6\n2 2\n255\n"));
+        assert_eq!(&ppm[ppm.len() - 3..], &[0xFF, 0xFF, 0xFF]);
+
+        let png = encode_png(&frame);
+        assert_eq!(&png[..8], &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]);
+        assert_eq!(&png[12..16], b"IHDR");
+        // Le bloc IEND final est toujours identique, CRC compris
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+        assert_eq!(
+            &png[png.len() - 8..],
+            &[b'I', b'E', b'N', b'D', 0xAE, 0x42, 0x60, 0x82]
+        );
+    }
+
+    #[test]
+    fn test_gif_animation() {
+        let mut recorder = GifRecorder::new(10);
+        recorder.add_frame(Frame::new(3, 2, vec![0xFF_000000; 6]));
+        recorder.add_frame(Frame::new(3, 2, vec![0xFF_FFFFFF; 6]));
+        assert_eq!(recorder.len(), 2);
+
+        let gif = recorder.encode(4);
+        assert!(gif.starts_with(b"GIF89a"));
+        assert_eq!(&gif[6..10], &[12, 0, 8, 0]);
+        assert!(gif.iter().filter(|&&byte| byte == 0x2C).count() >= 2);
+        assert_eq!(gif.last(), Some(&0x3B));
+    }
+}
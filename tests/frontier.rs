@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod frontier_tests {
+
+    use erea::ascii_map::parse_ascii_map;
+    use erea::base::Base;
+    use erea::behavior::Knowledge;
+    use erea::clock::Clock;
+    use erea::frontier::FrontierIndex;
+    use erea::resources::ResourceRegistry;
+    use erea::terrain::Terrain;
+    use erea::{generate_map_seeded, is_map_fully_explored, reveal_around};
+
+    #[test]
+    fn test_index_matches_full_scan() {
+        let mut map = generate_map_seeded(60, 45, ResourceRegistry::standard(), 11);
+        let clock = Clock::default();
+        let base = map.base;
+        reveal_around(&mut map, base, 4);
+        let mut index = FrontierIndex::new(&map);
+
+        // Après chaque découverte, l'index donne la même case que le parcours de toute la carte
+        for position in [(30, 22), (40, 10), (5, 40), (59, 44), (17, 3), (0, 0)] {
+            reveal_around(&mut map, position, 5);
+            index.update_around(&map, position, 5);
+            let scan = Knowledge::new(&map, &clock);
+            let indexed = Knowledge::new(&map, &clock).with_frontier(&index);
+            for from in [(30, 22), (40, 10), (1, 1), (59, 0), (45, 30), position] {
+                assert_eq!(
+                    indexed.nearest_unexplored(from),
+                    scan.nearest_unexplored(from)
+                );
+            }
+            let unexplored = map.cells().iter().filter(|cell| !cell.explored).count();
+            assert_eq!(index.unexplored(), unexplored);
+        }
+        assert_eq!(index.rebuilds, 1);
+    }
+
+    #[test]
+    fn test_update_catches_direct_changes() {
+        let mut map = generate_map_seeded(20, 20, ResourceRegistry::standard(), 2);
+        let mut index = FrontierIndex::new(&map);
+
+        map.set_terrain((5, 5), Terrain::Rock);
+        map.explore_all();
+        let cells: Vec<(usize, usize)> = map.positions().collect();
+        index.update(&map, cells);
+        assert!(index.is_complete());
+        assert!(map.positions().all(|cell| !map.needs_exploration(cell)));
+        assert_eq!(index.nearest((3, 3)), None);
+        assert_eq!(index.cells().count(), 0);
+        assert_eq!(index.rebuilds, 1);
+    }
+
+    #[test]
+    fn test_unreachable_cells_stay_unexplored() {
+        let mut map = parse_ascii_map(
+            "B.###\n\
+             ..#..\n\
+             ..###\n",
+        )
+        .unwrap();
+        reveal_around(&mut map, (0, 0), 2);
+        reveal_around(&mut map, (4, 0), 0);
+        reveal_around(&mut map, (4, 2), 0);
+        let mut base = Base::new();
+        base.prepare(&mut map);
+
+        // La poche derrière la roche n'est ni explorée ni à explorer
+        assert!(!map.is_explored((3, 1)) && map.is_unreachable((3, 1)));
+        assert!(!map.needs_exploration((4, 1)));
+        assert!(is_map_fully_explored(&map, &base));
+        let index = FrontierIndex::new(&map);
+        assert!(index.is_complete());
+
+        // La roche effacée, la poche redevient à explorer
+        map.set_terrain((2, 1), Terrain::Plain);
+        base.edited(&mut map, &[(2, 1)]);
+        assert!(map.needs_exploration((3, 1)));
+        assert!(!is_map_fully_explored(&map, &base));
+    }
+
+    #[test]
+    fn test_sealed_pocket_is_abandoned_locally() {
+        let mut map = parse_ascii_map(
+            "B.###\n\
+             ..#..\n\
+             ..###\n",
+        )
+        .unwrap();
+        let mut base = Base::new();
+        base.home.rebuild(&map);
+        base.frontier.rebuild(&map);
+
+        // La poche est repérée dès que la roche qui la ferme est découverte, sans parcourir
+        // la carte
+        reveal_around(&mut map, (0, 0), 2);
+        base.revealed(&mut map, (0, 0), 2);
+        assert!(map.is_unreachable((3, 1)) && map.is_unreachable((4, 1)));
+        assert!(base.frontier.is_complete());
+        for position in [(4, 0), (4, 2)] {
+            reveal_around(&mut map, position, 0);
+            base.revealed(&mut map, position, 0);
+        }
+        assert!(base.frontier.is_complete());
+        assert_eq!(base.frontier.rebuilds, 1);
+        assert_eq!(base.home.rebuilds, 1);
+
+        // La roche effacée à l'éditeur, la poche redevient à explorer
+        map.set_terrain((2, 1), Terrain::Plain);
+        base.edited(&mut map, &[(2, 1)]);
+        assert!(map.needs_exploration((3, 1)));
+        assert!(!base.frontier.is_complete());
+        assert_eq!(base.frontier.unexplored(), 4);
+    }
+}
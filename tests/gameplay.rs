@@ -1,19 +1,24 @@
 #[cfg(test)]
 mod tests {
-    use super::*;
-    use erea::Map;
+    use erea::empty_map;
     use erea::Robot;
     use erea::Task;
+    use erea::terrain::Terrain;
+    use erea::resources::MINERALS;
     use std::process::Command;
     use erea::generate_map;
+    use erea::generate_map_seeded;
+    use erea::resources::ResourceRegistry;
     use erea::explore_map;
+    use erea::base::Base;
     use erea::collect_resources;
+    use erea::clock::Clock;
 
     #[test]
     fn test_game_start() {
-        // Exécute le programme principal
+        // Exécute le programme principal sans fenêtre, pour ne pas dépendre d'un affichage
         let output = Command::new("cargo")
-            .arg("run")
+            .args(["run", "--", "--headless", "20"])
             .output()
             .expect("Failed to execute command");
         
@@ -28,17 +33,17 @@ mod tests {
     fn test_explore_map() {
         let mut map = generate_map(10, 10);
         let mut robot = Robot::new(5, 5, Task::Explore);
-        let initial_explored = map.explored.clone();
+        let initial_explored = map.cells().to_vec();
 
         
-        explore_map(&mut robot, &mut map);
+        explore_map(&mut robot, &mut map, &mut Base::new(), &Clock::default());
 
         // Vérifie que la carte explorée a changé autour du robot
         for dy in -1..=1 {
             for dx in -1..=1 {
                 let x = (robot.x as isize + dx) as usize;
                 let y = (robot.y as isize + dy) as usize;
-                assert_ne!(map.explored[y][x], initial_explored[y][x]);
+                assert_ne!(map.is_explored((x, y)), initial_explored[map.index((x, y))].explored);
             }
         }
     }
@@ -54,22 +59,15 @@ mod tests {
         // Vérifie que la carte a une base définie
         assert_ne!(map.base, (0, 0));
 
-        // Vérifie que la carte a des obstacles
-        assert!(map.obstacles.iter().any(|row| row.iter().any(|&val| val)));
+        // Vérifie que la carte a des obstacles, sur une graine connue pour en placer
+        let map = generate_map_seeded(20, 20, ResourceRegistry::standard(), 7);
+        assert!(map.cells().iter().any(|cell| cell.terrain == Terrain::Rock));
     }
 
     #[test]
     fn test_robot_movement() {
         // Création d'une carte de test
-        let mut map = Map {
-            width: 10,
-            height: 10,
-            obstacles: vec![vec![false; 10]; 10],
-            energy: vec![],
-            minerals: vec![],
-            base: (5, 5),
-            explored: vec![vec![false; 10]; 10], // Initialisation de la carte explorée
-        };
+        let _map = empty_map(10, 10); // Carte vide et inexplorée, base en (5, 5)
 
         // Création d'un robot
         let mut robot = Robot::new(5, 5, Task::Explore);
@@ -99,15 +97,9 @@ mod tests {
     #[test]
         fn test_base_appearance() {
             // Création d'une carte de test
-            let map = Map {
-                width: 10,
-                height: 10,
-                obstacles: vec![vec![false; 10]; 10],
-                energy: vec![],
-                minerals: vec![],
-                base: (5, 5), // Position de la base scientifique
-                explored: vec![vec![true; 10]; 10], // Toutes les cases sont explorées
-            };
+            let mut map = empty_map(10, 10);
+            map.base = (5, 5); // Position de la base scientifique
+            map.explore_all(); // Toutes les cases sont explorées
 
             // Vérification que la position de la base est correcte
             assert_eq!(map.base, (5, 5));
@@ -116,27 +108,24 @@ mod tests {
         #[test]
         fn test_mineral_presence() {
             // Création d'une carte de test
-            let mut map = Map {
-                width: 10,
-                height: 10,
-                obstacles: vec![vec![false; 10]; 10],
-                energy: vec![],
-                minerals: vec![(5, 5)], // Ajout d'un minerai à la position (5, 5)
-                base: (5, 5),
-                explored: vec![vec![false; 10]; 10],
-            };
+            let mut map = empty_map(10, 10);
+            map.add_deposit(MINERALS, (5, 5)); // Ajout d'un minerai à la position (5, 5)
     
             // Vérification de la présence du minerai
-            assert!(map.minerals.contains(&(5, 5)));
+            assert!(map.deposits(MINERALS).contains(&(5, 5)));
         } 
         
         #[test]
 fn test_robot_collects_minerals() {
-    let mut map = generate_map(10, 10);
-    map.minerals.push((5, 6));
-    let mut robot = Robot::new(5, 5, Task::CollectMinerals);
-    collect_resources(&mut robot, &mut map);
-    assert_eq!(robot.minerals, 1);
+    let mut map = generate_map_seeded(10, 10, ResourceRegistry::standard(), 1);
+    map.set_terrain((5, 6), Terrain::Plain);
+    map.add_deposit(MINERALS, (5, 6));
+    let mut robot = Robot::new(5, 5, Task::Collect(MINERALS));
+    // Le robot rejoint le gisement voisin, puis le fore
+    for _ in 0..5 {
+        collect_resources(&mut robot, &mut map, &Clock::default());
+    }
+    assert_eq!(robot.carried(MINERALS), 1);
     
 }
 }
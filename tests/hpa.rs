@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod hpa_tests {
+
+    use erea::base::Base;
+    use erea::hpa::Hierarchy;
+    use erea::pathfinding::{find_path, path_cost};
+    use erea::resources::ResourceRegistry;
+    use erea::terrain::Terrain;
+    use erea::{generate_map_seeded, Map};
+
+    // Vérifie qu'un chemin avance case par case sur des cases franchissables jusqu'au but
+    fn assert_walkable(
+        map: &Map,
+        start: (usize, usize),
+        goal: (usize, usize),
+        path: &[(usize, usize)],
+    ) {
+        let mut cell = start;
+        for &next in path {
+            assert!(cell.0.abs_diff(next.0) <= 1 && cell.1.abs_diff(next.1) <= 1);
+            assert!(map.known_cost(next).is_some());
+            cell = next;
+        }
+        assert_eq!(cell, goal);
+    }
+
+    #[test]
+    fn test_paths_are_close_to_astar() {
+        let mut map = generate_map_seeded(64, 64, ResourceRegistry::standard(), 7);
+        map.explore_all();
+        let hierarchy = Hierarchy::with_cost(&map, 8, Map::known_cost);
+        assert!(hierarchy.entrances() > 0);
+
+        for (start, goal) in [
+            ((0, 0), (63, 63)),
+            ((5, 60), (60, 2)),
+            ((30, 31), (33, 30)),
+            ((1, 32), (62, 32)),
+        ] {
+            let expected = find_path(&map, start, goal);
+            let path = hierarchy.find_path(&map, start, goal);
+            assert_eq!(path.is_some(), expected.is_some());
+            if let (Some(path), Some(expected)) = (path, expected) {
+                assert_walkable(&map, start, goal, &path);
+                // Le chemin hiérarchique est au plus un quart plus cher que l'optimal
+                let (cost, best) = (path_cost(&map, &path), path_cost(&map, &expected));
+                assert!(
+                    cost >= best && cost * 4 <= best * 5,
+                    "{} contre {}",
+                    cost,
+                    best
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalidation_rebuilds_changed_clusters() {
+        let mut map = generate_map_seeded(32, 32, ResourceRegistry::standard(), 4);
+        for y in 0..map.height {
+            for x in 0..map.width {
+                map.set_terrain((x, y), Terrain::Plain);
+            }
+        }
+        let mut hierarchy = Hierarchy::with_cost(&map, 8, Map::known_cost);
+        let rebuilt = hierarchy.rebuilt;
+
+        // Une case révélée sans changer son coût ne marque rien
+        map.set_explored((10, 10), true);
+        hierarchy.invalidate(&map, [(10, 10)]);
+        hierarchy.refresh(&map);
+        assert_eq!(hierarchy.rebuilt, rebuilt);
+
+        // Un mur découvert dans un seul groupe ne fait reconstruire que ce groupe
+        for y in 8..16 {
+            map.set_terrain((12, y), Terrain::Rock);
+            map.set_explored((12, y), true);
+        }
+        hierarchy.invalidate(&map, (8..16).map(|y| (12, y)));
+        hierarchy.refresh(&map);
+        assert_eq!(hierarchy.rebuilt, rebuilt + 1);
+
+        let (start, goal) = ((9, 12), (15, 12));
+        let path = hierarchy.find_path(&map, start, goal).unwrap();
+        assert_walkable(&map, start, goal, &path);
+        // Le mur coupe le groupe : le chemin le contourne
+        assert!(path.len() > 6);
+    }
+
+    #[test]
+    fn test_edits_reach_the_hierarchy() {
+        let mut map = generate_map_seeded(32, 32, ResourceRegistry::standard(), 4);
+        for y in 0..map.height {
+            for x in 0..map.width {
+                map.set_terrain((x, y), Terrain::Plain);
+            }
+        }
+        map.explore_all();
+        let mut base = Base::new();
+        base.hierarchy = Some(Hierarchy::with_cost(&map, 8, Map::known_cost));
+        let rebuilt = base.hierarchy.as_ref().unwrap().rebuilt;
+
+        // Un mur posé à l'éditeur est signalé à la base, qui ne reconstruit que son groupe
+        let wall: Vec<(usize, usize)> = (8..16).map(|y| (12, y)).collect();
+        for &cell in &wall {
+            map.set_terrain(cell, Terrain::Rock);
+        }
+        base.edited(&mut map, &wall);
+        let hierarchy = base.hierarchy.as_ref().unwrap();
+        assert_eq!(hierarchy.rebuilt, rebuilt + 1);
+
+        let (start, goal) = ((9, 12), (15, 12));
+        let path = hierarchy.find_path(&map, start, goal).unwrap();
+        assert_walkable(&map, start, goal, &path);
+        assert!(path.len() > 6);
+    }
+}
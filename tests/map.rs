@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod map_tests {
+
+    use erea::ascii_map::parse_ascii_map;
+    use erea::terrain::Terrain;
+
+    // Carte non carrée : 5 colonnes sur 3 rangées
+    fn wide_map() -> erea::Map {
+        parse_ascii_map(
+            "B.:^.\n\
+             .#...\n\
+             ....^\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_neighbors_at_edges_and_corners() {
+        let map = wide_map();
+        let sorted = |position| {
+            let mut cells: Vec<(usize, usize)> = map.neighbors(position).collect();
+            cells.sort();
+            cells
+        };
+
+        // Coins : trois voisines seulement
+        assert_eq!(sorted((0, 0)), vec![(0, 1), (1, 0), (1, 1)]);
+        assert_eq!(sorted((4, 2)), vec![(3, 1), (3, 2), (4, 1)]);
+        assert_eq!(sorted((4, 0)), vec![(3, 0), (3, 1), (4, 1)]);
+        assert_eq!(sorted((0, 2)), vec![(0, 1), (1, 1), (1, 2)]);
+
+        // Bords : cinq voisines
+        assert_eq!(sorted((2, 0)), vec![(1, 0), (1, 1), (2, 1), (3, 0), (3, 1)]);
+        assert_eq!(sorted((4, 1)), vec![(3, 0), (3, 1), (3, 2), (4, 0), (4, 2)]);
+
+        // Intérieur : huit voisines, sans la case elle-même
+        let inner = sorted((2, 1));
+        assert_eq!(inner.len(), 8);
+        assert!(!inner.contains(&(2, 1)));
+        assert!(inner
+            .iter()
+            .all(|&(x, y)| x.abs_diff(2) <= 1 && y.abs_diff(1) <= 1));
+    }
+
+    #[test]
+    fn test_index_and_positions_round_trip() {
+        let map = wide_map();
+        let positions: Vec<(usize, usize)> = map.positions().collect();
+        assert_eq!(positions.len(), map.width * map.height);
+        assert_eq!(positions.len(), map.cells().len());
+        assert_eq!(positions[0], (0, 0));
+        assert_eq!(positions[map.width], (0, 1));
+        assert_eq!(positions.last(), Some(&(4, 2)));
+
+        // L'indice d'une position est son rang dans le parcours ligne par ligne
+        for (i, &position) in positions.iter().enumerate() {
+            assert_eq!(map.index(position), i);
+            assert_eq!(
+                map.cell(position).terrain,
+                map.cells()[i].terrain,
+                "case {position:?}"
+            );
+        }
+        assert_eq!(map.cell((1, 1)).terrain, Terrain::Rock);
+        assert_eq!(map.cell((4, 2)).terrain, Terrain::Rough);
+    }
+
+    #[test]
+    fn test_rows_cover_the_map_width() {
+        let map = wide_map();
+        for y in 0..map.height {
+            let row = map.row(y);
+            assert_eq!(row.len(), map.width);
+            for (x, cell) in row.iter().enumerate() {
+                assert_eq!(cell.terrain, map.cell((x, y)).terrain);
+            }
+        }
+        assert_eq!(map.row(0)[2].terrain, Terrain::Sand);
+        assert_eq!(map.row(2)[4].terrain, Terrain::Rough);
+        let rows: usize = (0..map.height).map(|y| map.row(y).len()).sum();
+        assert_eq!(rows, map.cells().len());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_row_past_the_last_one_panics() {
+        let map = wide_map();
+        map.row(map.height);
+    }
+}
@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod mapf_tests {
+
+    use erea::action::{Action, Direction};
+    use erea::ascii_map::parse_ascii_map;
+    use erea::base::Base;
+    use erea::behavior::{Behavior, Knowledge, RobotView};
+    use erea::clock::Clock;
+    use erea::distance_field::DistanceField;
+    use erea::editor::reveal;
+    use erea::mapf::{plan_path, plan_path_with, Heuristics, Reservations, DEADLOCK_AFTER};
+    use erea::traffic::Traffic;
+    use erea::{simulate_tick, Map, Robot, Task};
+
+    // Stratégie de test : toujours la même action
+    struct Repeat(Action);
+
+    impl Behavior for Repeat {
+        fn name(&self) -> &str {
+            "repeat"
+        }
+
+        fn decide(&mut self, _robot: &RobotView, _knowledge: &Knowledge) -> Action {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_robots_cross_in_corridor() {
+        let mut map = parse_ascii_map(
+            "......\n\
+             ##.###\n\
+             #####B\n",
+        )
+        .unwrap();
+        reveal(&mut map);
+        let mut reservations = Reservations::new(map.base);
+
+        // Le premier robot traverse le couloir, le second s'efface dans l'alcôve
+        let first = plan_path(&map, &reservations, (0, 0), (5, 0), 16).unwrap();
+        assert_eq!(first[5], (5, 0));
+        reservations.reserve(&first);
+        let second = plan_path(&map, &reservations, (5, 0), (0, 0), 16).unwrap();
+        assert!(second.contains(&(2, 1)));
+        assert_eq!(second[16], (0, 0));
+        for t in 0..16 {
+            assert_ne!(first[t], second[t]);
+            assert!(!(first[t] == second[t + 1] && first[t + 1] == second[t]));
+        }
+    }
+
+    #[test]
+    fn test_deadlock_is_reported() {
+        let mut map = parse_ascii_map("B...\n").unwrap();
+        reveal(&mut map);
+        let mut pusher = Robot::new(1, 0, Task::Explore);
+        pusher.behavior = Some(Box::new(Repeat(Action::Move(Direction::East))));
+        let mut holder = Robot::new(2, 0, Task::Explore);
+        holder.behavior = Some(Box::new(Repeat(Action::Wait)));
+        let mut robots = vec![pusher, holder];
+        let mut base = Base::new();
+        base.traffic = Traffic::planned(8);
+        let mut clock = Clock::default();
+
+        // Le robot poussé ne bouge pas : l'interblocage est signalé une seule fois
+        for _ in 0..DEADLOCK_AFTER {
+            simulate_tick(&mut map, &mut robots, &mut base, &mut clock);
+        }
+        assert_eq!((robots[0].x, robots[0].y), (1, 0));
+        let fleet = base.traffic.fleet.as_ref().unwrap();
+        assert_eq!(fleet.deadlocks.len(), 1);
+        assert_eq!(fleet.deadlocks[0].tick, DEADLOCK_AFTER - 1);
+        assert_eq!(fleet.deadlocks[0].robots, [0]);
+        assert_eq!(fleet.deadlocks[0].cells, [(1, 0)]);
+        assert_eq!(fleet.backoffs, 0);
+
+        // Au tour suivant, il recule vers la seule case libre
+        simulate_tick(&mut map, &mut robots, &mut base, &mut clock);
+        assert_eq!((robots[0].x, robots[0].y), (0, 0));
+        assert_eq!(robots[0].blocked, 0);
+        let fleet = base.traffic.fleet.as_ref().unwrap();
+        assert_eq!(fleet.backoffs, 1);
+        assert_eq!(fleet.deadlocks.len(), 1);
+    }
+
+    #[test]
+    fn test_distance_fields_are_reused() {
+        let mut map = parse_ascii_map(
+            "B.....\n\
+             ......\n\
+             ......\n",
+        )
+        .unwrap();
+        reveal(&mut map);
+        let reservations = Reservations::new(map.base);
+        let mut heuristics = Heuristics::default();
+
+        // Plusieurs recherches vers la même cible, sur plusieurs tours : un seul champ calculé
+        for start in [(0, 2), (1, 2), (2, 2)] {
+            heuristics.next_tick();
+            let path = plan_path_with(&mut heuristics, &map, &reservations, start, (5, 0), 8);
+            assert_eq!(path, plan_path(&map, &reservations, start, (5, 0), 8));
+        }
+        assert_eq!((heuristics.builds, heuristics.len()), (1, 1));
+
+        // Un terrain modifié répare le champ sur place, qui donne les mêmes distances qu'un
+        // champ recalculé ; une cible abandonnée est oubliée
+        map.set_terrain((3, 1), erea::terrain::Terrain::Rock);
+        heuristics.update(&[(3, 1)]);
+        heuristics.next_tick();
+        let path = plan_path_with(&mut heuristics, &map, &reservations, (0, 2), (5, 0), 8);
+        assert_eq!(path, plan_path(&map, &reservations, (0, 2), (5, 0), 8));
+        assert!(!path.unwrap().contains(&(3, 1)));
+        let fresh = DistanceField::towards(&map, (5, 0), Map::known_cost);
+        let field = heuristics.field(&map, (5, 0));
+        assert!(map
+            .positions()
+            .all(|cell| field.distance(cell) == fresh.distance(cell)));
+        assert_eq!(heuristics.builds, 1);
+        heuristics.next_tick();
+        plan_path_with(&mut heuristics, &map, &reservations, (0, 2), (4, 2), 8).unwrap();
+        heuristics.next_tick();
+        assert_eq!(heuristics.len(), 1);
+    }
+}
@@ -0,0 +1,168 @@
+#[cfg(test)]
+mod modules_tests {
+
+    use erea::ascii_map::parse_ascii_map;
+    use erea::base::Base;
+    use erea::clock::Clock;
+    use erea::editor::reveal;
+    use erea::modules::{chassis_cost, upgrade_cost, Cost, ModuleKind, Modules, MAX_LEVEL};
+    use erea::resources::{registry_from_str, ENERGY, ENERGY_KEY, MINERALS, MINERAL_KEY, SCIENCE};
+    use erea::{collect_resources, empty_map, explore_map, Robot, RobotState, Task};
+
+    #[test]
+    fn test_module_costs() {
+        assert_eq!(upgrade_cost(1), Cost::new(&[(ENERGY_KEY, 1)]));
+        assert_eq!(
+            upgrade_cost(3),
+            Cost::new(&[(ENERGY_KEY, 1), (MINERAL_KEY, 2)])
+        );
+
+        // Eclaireur : châssis, capteur 2, batterie 2, roues 2
+        let cost = Modules::scout().cost();
+        assert_eq!(
+            cost.amount(ENERGY_KEY),
+            chassis_cost().amount(ENERGY_KEY) + 6
+        );
+        assert_eq!(
+            cost.amount(MINERAL_KEY),
+            chassis_cost().amount(MINERAL_KEY) + 3
+        );
+    }
+
+    #[test]
+    fn test_base_fits_affordable_modules() {
+        let map = empty_map(4, 4);
+        let mut base = Base::new();
+        let mut robot = Robot::new(0, 0, Task::Collect(ENERGY));
+
+        // La foreuse standard est déjà au niveau maximal, la soute passe au niveau 2
+        assert!(!base.fit(&map, &mut robot, ModuleKind::Cargo));
+        base.store(ENERGY, 1);
+        base.store(MINERALS, 1);
+        assert_eq!(robot.modules.drill, MAX_LEVEL);
+        assert_eq!(
+            base.fit_best_upgrade(&map, &mut robot),
+            Some(ModuleKind::Cargo)
+        );
+        assert_eq!(robot.modules.cargo_capacity(), 2);
+        assert!(!base.can_afford(&map, &upgrade_cost(1)));
+    }
+
+    #[test]
+    fn test_costs_follow_registry_keys() {
+        // Un registre qui range les minerais avant l'énergie, avec une ressource de plus
+        let resources = registry_from_str(
+            "resource ice symbol=I\n\
+             resource mineral symbol=M\n\
+             resource energy symbol=E\n",
+        )
+        .unwrap();
+        let map = erea::empty_map_with(4, 4, resources);
+        let ice = map.resources.by_key("ice").unwrap();
+        let minerals = map.resources.by_key(MINERAL_KEY).unwrap();
+        let energy = map.resources.by_key(ENERGY_KEY).unwrap();
+        assert_eq!((energy, minerals), (SCIENCE, MINERALS));
+        let mut base = Base::new();
+        base.store(ice, 100);
+        assert!(!base.can_afford(&map, &upgrade_cost(2)));
+
+        // Le coût est prélevé sur les bonnes ressources
+        base.store(energy, 1);
+        base.store(minerals, 1);
+        assert!(base.can_afford(&map, &upgrade_cost(2)));
+        let mut robot = Robot::new(0, 0, Task::Explore);
+        assert!(base.fit(&map, &mut robot, ModuleKind::Sensor));
+        assert_eq!((base.stock(energy), base.stock(minerals)), (0, 0));
+        assert_eq!(base.stock(ice), 100);
+
+        // Sans énergie dans le registre, les modules ne peuvent pas être payés
+        let map = erea::empty_map_with(4, 4, registry_from_str("resource mineral\n").unwrap());
+        let mut base = Base::new();
+        base.store(ENERGY, 10);
+        assert!(!base.can_afford(&map, &upgrade_cost(1)));
+    }
+
+    #[test]
+    fn test_scout_sees_further_but_cannot_collect() {
+        let mut map = parse_ascii_map(
+            ".......\n\
+             ...B...\n\
+             ......E\n",
+        )
+        .unwrap();
+
+        let mut scout = Robot::with_modules(3, 1, Task::Explore, Modules::scout());
+        explore_map(&mut scout, &mut map, &mut Base::new(), &Clock::default());
+        assert!(map.is_explored((1, 1)) && map.is_explored((5, 1)));
+        assert!(!map.is_explored((0, 1)));
+
+        reveal(&mut map);
+        let mut scout = Robot::with_modules(6, 2, Task::Collect(ENERGY), Modules::scout());
+        scout.state = RobotState::Collecting;
+        collect_resources(&mut scout, &mut map, &Clock::default());
+        assert_eq!(scout.state, RobotState::Returning);
+        assert_eq!(map.deposits(ENERGY).len(), 1);
+    }
+
+    #[test]
+    fn test_drill_level_sets_collection_time() {
+        let mut map = parse_ascii_map("B.E\n").unwrap();
+        reveal(&mut map);
+
+        // Foreuse de niveau 2 : deux tours par unité
+        let mut miner = Robot::with_modules(2, 0, Task::Collect(ENERGY), Modules::miner());
+        miner.state = RobotState::Collecting;
+        collect_resources(&mut miner, &mut map, &Clock::default());
+        assert_eq!(miner.carried(ENERGY), 0);
+        collect_resources(&mut miner, &mut map, &Clock::default());
+        assert_eq!(miner.carried(ENERGY), 1);
+        assert!(map.deposits(ENERGY).is_empty());
+    }
+
+    #[test]
+    fn test_base_assembles_planned_robot() {
+        let mut map = parse_ascii_map("B..E\n....\n").unwrap();
+        let robots = vec![Robot::new(0, 0, Task::Explore)];
+        let mut base = Base::new();
+
+        // Carte inconnue : un éclaireur est prévu, mais le stock est vide
+        let (task, modules) = base.plan_robot(&map, &robots).unwrap();
+        assert_eq!(task, Task::Explore);
+        assert!(base.assemble(&map, task, modules).is_none());
+
+        reveal(&mut map);
+        base.store(ENERGY, 20);
+        base.store(MINERALS, 20);
+        let (task, modules) = base.plan_robot(&map, &robots).unwrap();
+        assert_eq!(task, Task::Collect(ENERGY));
+        let robot = base.assemble(&map, task, modules).unwrap();
+        assert_eq!((robot.x, robot.y), map.base);
+        assert_eq!(base.stock(ENERGY), 20 - modules.cost().amount(ENERGY_KEY));
+    }
+
+    #[test]
+    fn test_researcher_brings_back_research_points() {
+        let mut map = parse_ascii_map("BS\n").unwrap();
+        reveal(&mut map);
+        let mut base = Base::new();
+
+        // Les mineurs n'ont pas de module d'analyse
+        assert!(
+            !Robot::with_modules(1, 0, Task::Collect(SCIENCE), Modules::miner()).can_collect(&map)
+        );
+
+        // Module d'analyse de niveau 2 : quatre tours par échantillon
+        let mut robot = Robot::with_modules(1, 0, Task::Collect(SCIENCE), Modules::researcher());
+        robot.state = RobotState::Collecting;
+        for _ in 0..4 {
+            collect_resources(&mut robot, &mut map, &Clock::default());
+        }
+        assert_eq!(robot.carried(SCIENCE), 1);
+        assert!(map.deposits(SCIENCE).is_empty());
+        assert_eq!(robot.state, RobotState::Returning);
+
+        base.unload(&mut robot);
+        assert_eq!(base.stock(SCIENCE), 1);
+        assert_eq!(robot.cargo(), 0);
+    }
+}
@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod resources_tests {
+
+    use erea::ascii_map::{map_to_ascii, parse_ascii_map_with};
+    use erea::clock::Clock;
+    use erea::editor::reveal;
+    use erea::resources::{
+        load_registry, registry_from_str, Extraction, ResourceKind, ResourceRegistry, ENERGY,
+        MINERALS, SCIENCE,
+    };
+    use erea::{
+        collect_resources, generate_map_with, regenerate_deposits, Robot, RobotState, Task,
+    };
+
+    #[test]
+    fn test_standard_registry() {
+        let registry = ResourceRegistry::standard();
+        assert_eq!(registry.len(), 3);
+        assert_eq!(registry.by_key("energy"), Some(ENERGY));
+        assert_eq!(registry.by_symbol('s'), Some(SCIENCE));
+        assert_eq!(registry.get(SCIENCE).extraction, Extraction::Analysis);
+    }
+
+    #[test]
+    fn test_registry_from_config() {
+        let registry = load_registry("maps/resources.cfg").unwrap();
+        let ice = registry.by_key("ice").unwrap();
+        assert_eq!(ice, ResourceKind(3));
+        assert_eq!(registry.get(ice).symbol, 'I');
+        assert_eq!(registry.get(ice).color, 0xFF_88CCFF);
+        assert_eq!(registry.get(ice).min_distance, 8);
+
+        // Clé en double, symbole réservé, paramètre inconnu
+        assert!(registry_from_str("resource a\nresource a\n").is_err());
+        assert!(registry_from_str("resource a symbol=B\n").is_err());
+        assert!(registry_from_str("resource a speed=2\n").is_err());
+    }
+
+    #[test]
+    fn test_new_resource_without_new_code_path() {
+        let registry = registry_from_str("resource ice symbol=I rarity=20 distance=3\n").unwrap();
+        let ice = ResourceKind(0);
+
+        // Génération : au moins un gisement, loin de la base
+        let map = generate_map_with(20, 20, registry.clone());
+        assert!(!map.deposits(ice).is_empty());
+        assert!(map
+            .deposits(ice)
+            .iter()
+            .all(|&(x, y)| x.abs_diff(map.base.0) + y.abs_diff(map.base.1) >= 3));
+
+        // Format ASCII et collecte
+        let mut map = parse_ascii_map_with("B.I\n..i\n", &registry).unwrap();
+        assert_eq!(map.deposits(ice), [(2, 0), (2, 1)]);
+        assert!(map.is_obstacle((2, 1)));
+        assert_eq!(map_to_ascii(&map), "B.I\n..i\n");
+
+        reveal(&mut map);
+        let mut robot = Robot::new(0, 0, Task::Collect(ice));
+        robot.state = RobotState::Collecting;
+        for _ in 0..3 {
+            collect_resources(&mut robot, &mut map, &Clock::default());
+        }
+        assert_eq!(robot.carried(ice), 1);
+        assert_eq!(map.deposits(ice), [(2, 1)]);
+    }
+
+    #[test]
+    fn test_energy_regenerates_but_minerals_do_not() {
+        let mut map = parse_ascii_map_with("BEM\n", &ResourceRegistry::standard()).unwrap();
+        let regeneration = map.resources.get(ENERGY).regeneration;
+        map.deplete(ENERGY, (1, 0));
+        map.deplete(MINERALS, (2, 0));
+        assert!(map.deposits(ENERGY).is_empty());
+        assert_eq!(map.depleted.len(), 1);
+
+        // L'énergie est solaire : la nuit, le compteur ne bouge pas
+        regenerate_deposits(&mut map, false);
+        assert_eq!(map.depleted[0].remaining, regeneration);
+
+        for _ in 0..regeneration {
+            regenerate_deposits(&mut map, true);
+        }
+        assert_eq!(map.deposits(ENERGY), [(1, 0)]);
+        assert!(map.deposits(MINERALS).is_empty());
+        assert!(map.depleted.is_empty());
+    }
+}
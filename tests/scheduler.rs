@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod scheduler_tests {
+
+    use erea::base::Base;
+    use erea::clock::Clock;
+    use erea::editor::reveal;
+    use erea::modules::Modules;
+    use erea::resources::{ENERGY, MINERALS};
+    use erea::scheduler::{schedule, BalancedPolicy, FixedPolicy, Policy, Scheduler};
+    use erea::{empty_map, Robot, RobotState, Task};
+
+    #[test]
+    fn test_balanced_policy_follows_needs() {
+        let mut map = empty_map(10, 10);
+        map.add_deposit(ENERGY, (2, 2));
+        map.add_deposit(MINERALS, (7, 7));
+        let mut robots = vec![
+            Robot::with_modules(0, 0, Task::Explore, Modules::scout()),
+            Robot::new(0, 0, Task::Collect(ENERGY)),
+            Robot::new(0, 0, Task::Collect(ENERGY)),
+        ];
+        let mut base = Base::new();
+
+        // Carte inconnue : les collecteurs partent explorer avec l'éclaireur
+        let tasks = BalancedPolicy.assign(&map, &robots, &base);
+        assert_eq!(tasks, [Task::Explore; 3]);
+
+        // Carte explorée et énergie déjà en stock : un collecteur passe aux minerais
+        reveal(&mut map);
+        base.store(ENERGY, 1);
+        let clock = Clock::default();
+        assert_eq!(schedule(&map, &mut robots, &mut base, &clock), 1);
+        assert_eq!(robots[0].task, Task::Explore);
+        assert_eq!(robots[1].task, Task::Collect(ENERGY));
+        assert_eq!(robots[2].task, Task::Collect(MINERALS));
+        assert_eq!(robots[2].state, RobotState::Collecting);
+    }
+
+    #[test]
+    fn test_fixed_policy_and_period() {
+        let mut map = empty_map(6, 6);
+        map.add_deposit(MINERALS, (4, 4));
+        reveal(&mut map);
+        let mut robots = vec![Robot::new(0, 0, Task::Collect(ENERGY))];
+        let mut base = Base::new();
+        let mut clock = Clock::default();
+        clock.advance();
+
+        // En dehors de la période, le planificateur ne fait rien
+        assert_eq!(schedule(&map, &mut robots, &mut base, &clock), 0);
+        assert_eq!(robots[0].task, Task::Collect(ENERGY));
+
+        // La politique fixe garde la tâche de construction
+        base.scheduler = Scheduler::new(Box::new(FixedPolicy), 1);
+        assert_eq!(schedule(&map, &mut robots, &mut base, &clock), 0);
+        assert_eq!(robots[0].task, Task::Collect(ENERGY));
+    }
+}
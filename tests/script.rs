@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod script_tests {
+
+    use erea::action::{Action, Direction};
+    use erea::ascii_map::parse_ascii_map;
+    use erea::behavior::{Behavior, Knowledge, RobotView};
+    use erea::clock::Clock;
+    use erea::editor::reveal;
+    use erea::resources::ENERGY;
+    use erea::script::ScriptBehavior;
+    use erea::{Robot, Task};
+    use std::fs;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn test_script_decides_actions() {
+        let mut map = parse_ascii_map("B..E\n").unwrap();
+        reveal(&mut map);
+        let clock = Clock::default();
+        let robot = Robot::new(0, 0, Task::Collect(ENERGY));
+        let view = RobotView::of(&robot, &map, 1);
+        let knowledge = Knowledge::new(&map, &clock);
+
+        let mut script = ScriptBehavior::from_source(
+            "fn decide(robot, world) { #{ action: \"travel\", x: world.deposit.x, y: robot.y } }",
+        )
+        .unwrap();
+        assert_eq!(
+            script.decide(&view, &knowledge),
+            Action::Move(Direction::East)
+        );
+        assert!(script.error.is_none());
+
+        // Le script d'exemple collecte dès qu'un gisement est connu
+        let mut script = ScriptBehavior::load("scripts/robot.rhai").unwrap();
+        assert_eq!(
+            script.decide(&view, &knowledge),
+            Action::Move(Direction::East)
+        );
+        assert!(script.error.is_none());
+
+        // Sans fonction decide, le script est refusé
+        assert!(ScriptBehavior::from_source("let x = 1;").is_err());
+    }
+
+    #[test]
+    fn test_script_is_sandboxed() {
+        let mut map = parse_ascii_map("B..\n").unwrap();
+        map.set_explored((0, 0), true);
+        let clock = Clock::default();
+        let robot = Robot::new(0, 0, Task::Explore);
+        let view = RobotView::of(&robot, &map, 1);
+        let knowledge = Knowledge::new(&map, &clock);
+
+        // Une boucle infinie est interrompue : le robot suit alors la stratégie de sa tâche
+        let mut script =
+            ScriptBehavior::from_source("fn decide(robot, world) { loop {} }").unwrap();
+        assert_eq!(
+            script.decide(&view, &knowledge),
+            Action::Move(Direction::East)
+        );
+        assert!(script.error.is_some());
+
+        // Une destination hors de la carte est refusée
+        let mut script = ScriptBehavior::from_source(
+            "fn decide(robot, world) { #{ action: \"travel\", x: 99, y: 0 } }",
+        )
+        .unwrap();
+        assert_eq!(
+            script.decide(&view, &knowledge),
+            Action::Move(Direction::East)
+        );
+        assert!(script.error.is_some());
+    }
+
+    #[test]
+    fn test_script_hot_reload() {
+        let mut map = parse_ascii_map("B..\n").unwrap();
+        map.set_explored((0, 0), true);
+        let clock = Clock::default();
+        let robot = Robot::new(0, 0, Task::Explore);
+        let view = RobotView::of(&robot, &map, 1);
+        let knowledge = Knowledge::new(&map, &clock);
+
+        let path = std::env::temp_dir().join(format!("erea_reload_{}.rhai", std::process::id()));
+        fs::write(&path, "fn decide(robot, world) { \"wait\" }").unwrap();
+        let mut script = ScriptBehavior::load(&path).unwrap();
+        assert_eq!(script.decide(&view, &knowledge), Action::Wait);
+
+        // Un script modifié est rechargé au tour suivant, un script invalide est ignoré
+        let touch = |delay: u64| {
+            let file = fs::File::options().write(true).open(&path).unwrap();
+            file.set_modified(SystemTime::now() + Duration::from_secs(delay))
+                .unwrap();
+        };
+        fs::write(&path, "fn decide(robot, world) { \"east\" }").unwrap();
+        touch(10);
+        assert_eq!(
+            script.decide(&view, &knowledge),
+            Action::Move(Direction::East)
+        );
+        fs::write(&path, "fn decide(robot, world) {").unwrap();
+        touch(20);
+        assert_eq!(
+            script.decide(&view, &knowledge),
+            Action::Move(Direction::East)
+        );
+        assert!(script.error.is_some());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod terrain_tests {
+
+    use erea::ascii_map::parse_ascii_map;
+    use erea::editor::reveal;
+    use erea::pathfinding::{find_path, path_cost};
+    use erea::resources::ResourceRegistry;
+    use erea::terrain::Terrain;
+    use erea::{generate_map_seeded, Robot, Task, BATTERY_CAPACITY};
+
+    #[test]
+    fn test_terrain_bands() {
+        assert_eq!(Terrain::from_noise(0.0), Terrain::Plain);
+        assert_eq!(Terrain::from_noise(0.2), Terrain::Sand);
+        assert_eq!(Terrain::from_noise(0.4), Terrain::Rough);
+        assert_eq!(Terrain::from_noise(0.6), Terrain::Rock);
+        assert_eq!(Terrain::Rock.cost(), None);
+    }
+
+    #[test]
+    fn test_path_avoids_rock_and_rough() {
+        let mut map = parse_ascii_map(
+            "B^^^.\n\
+             .###.\n\
+             .....\n",
+        )
+        .unwrap();
+        reveal(&mut map);
+
+        // Le détour par le bas (5 cases de plaine) coûte moins que le terrain accidenté
+        let path = find_path(&map, (0, 0), (4, 0)).unwrap();
+        assert_eq!(path.last(), Some(&(4, 0)));
+        assert!(path.iter().all(|&(x, y)| !map.is_obstacle((x, y))));
+        assert_eq!(path_cost(&map, &path), 6);
+
+        // Une case de roche n'est jamais une destination
+        assert!(find_path(&map, (0, 0), (2, 1)).is_none());
+    }
+
+    #[test]
+    fn test_unexplored_cells_are_assumed_plain() {
+        let mut map = parse_ascii_map("B.#.\n").unwrap();
+        map.set_explored((0, 0), true);
+
+        // La roche inexplorée est traversée par le plan, puis découverte au contact
+        let mut robot = Robot::new(0, 0, Task::Explore);
+        assert!(robot.travel_to(&mut map, (3, 0)));
+        assert_eq!((robot.x, robot.y), (1, 0));
+        assert!(robot.travel_to(&mut map, (3, 0)));
+        assert_eq!((robot.x, robot.y), (1, 0));
+        assert!(map.is_explored((2, 0)));
+        assert!(!robot.travel_to(&mut map, (3, 0)));
+    }
+
+    #[test]
+    fn test_battery_pays_terrain_cost() {
+        let mut map = parse_ascii_map("B:^.\n").unwrap();
+        reveal(&mut map);
+        let mut robot = Robot::new(0, 0, Task::Explore);
+
+        robot.travel_to(&mut map, (3, 0));
+        robot.travel_to(&mut map, (3, 0));
+        assert_eq!((robot.x, robot.y), (2, 0));
+        assert_eq!(robot.battery, BATTERY_CAPACITY - 2 - 3);
+
+        // Le retour coûte 2 + 1 : avec la réserve, il faut rentrer
+        robot.battery = 5;
+        assert!(robot.needs_recharge(&map));
+        robot.battery = 50;
+        assert!(!robot.needs_recharge(&map));
+    }
+
+    #[test]
+    fn test_seeded_maps_are_identical() {
+        let first = generate_map_seeded(20, 20, ResourceRegistry::standard(), 7);
+        let second = generate_map_seeded(20, 20, ResourceRegistry::standard(), 7);
+        assert_eq!(first.cells(), second.cells());
+        for kind in first.resources.kinds() {
+            assert_eq!(first.deposits(kind), second.deposits(kind));
+        }
+    }
+}
@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tour_tests {
+
+    use erea::ascii_map::parse_ascii_map;
+    use erea::base::Base;
+    use erea::clock::Clock;
+    use erea::distance_field::DistanceField;
+    use erea::editor::reveal;
+    use erea::modules::Modules;
+    use erea::resources::ENERGY;
+    use erea::scheduler::{FixedPolicy, Scheduler};
+    use erea::tour::{plan_tour, plan_tour_with, tour_cost, two_opt};
+    use erea::{empty_map, simulate_tick, Robot, RobotState, Task};
+
+    #[test]
+    fn test_tour_order_and_battery() {
+        let mut map = empty_map(11, 11);
+        reveal(&mut map);
+        let deposits = [(9, 5), (7, 5), (8, 5)];
+        let home = DistanceField::new(&map);
+        let home_cost = |cell| home.distance(cell);
+
+        // Le plus proche d'abord : l'aller suit la ligne des gisements
+        let tour = plan_tour(&map, map.base, &deposits, 3, 100, home_cost);
+        assert_eq!(tour, [(7, 5), (8, 5), (9, 5)]);
+        assert_eq!(tour_cost(&map, map.base, &tour, home_cost), Some(8));
+
+        // 2-opt défait un croisement
+        let mut stops = [(9, 5), (7, 5)];
+        two_opt((0, 5), &mut stops, (10, 5));
+        assert_eq!(stops, [(7, 5), (9, 5)]);
+
+        // La tournée est raccourcie jusqu'à tenir dans la batterie, réserve comprise
+        let tour = plan_tour(&map, map.base, &deposits, 3, 7, home_cost);
+        assert_eq!(tour, [(7, 5)]);
+        assert!(plan_tour(&map, map.base, &deposits, 3, 6, home_cost).is_empty());
+    }
+
+    #[test]
+    fn test_tour_is_trimmed_with_the_leg_costs_found() {
+        let mut map = empty_map(11, 11);
+        reveal(&mut map);
+        let deposits = [(7, 5), (8, 5), (9, 5)];
+        let home = DistanceField::new(&map);
+        let home_cost = |cell| home.distance(cell);
+
+        // Chaque trajet est annoncé à 4 par la recherche : la tournée est coupée d'après ces
+        // coûts, sans recalculer les chemins
+        let nearest = |_: (usize, usize), tour: &[(usize, usize)]| {
+            let stop = deposits.iter().copied().find(|stop| !tour.contains(stop))?;
+            Some((stop, 4))
+        };
+        let tour = plan_tour_with(&map, map.base, nearest, 3, 100, home_cost);
+        assert_eq!(tour, deposits);
+        // Deux étapes : 8 pour l'aller, 3 pour le retour et 3 de réserve ; les vrais chemins
+        // auraient laissé les trois étapes
+        let tour = plan_tour_with(&map, map.base, nearest, 3, 14, home_cost);
+        assert_eq!(tour, [(7, 5), (8, 5)]);
+    }
+
+    #[test]
+    fn test_collector_fills_cargo_before_returning() {
+        let mut map = empty_map(11, 11);
+        map.add_deposit(ENERGY, (7, 5));
+        map.add_deposit(ENERGY, (8, 5));
+        reveal(&mut map);
+        let mut robot = Robot::with_modules(5, 5, Task::Collect(ENERGY), Modules::miner());
+        robot.state = RobotState::Collecting;
+        let mut robots = vec![robot];
+        let mut base = Base::new();
+        base.scheduler = Scheduler::new(Box::new(FixedPolicy), 1);
+        let mut clock = Clock::default();
+
+        // Un seul voyage rapporte les deux unités
+        for _ in 0..40 {
+            simulate_tick(&mut map, &mut robots, &mut base, &mut clock);
+        }
+        assert_eq!(base.stock(ENERGY), 2);
+        assert_eq!(base.trips.trips, 1);
+        assert_eq!(base.trips.units_per_trip(), 2.0);
+        assert_eq!(base.trips.spent, 6);
+    }
+
+    #[test]
+    fn test_tour_starts_with_the_nearest_deposit_by_path() {
+        let mut map = parse_ascii_map(
+            "..#.......\n\
+             .E#.......\n\
+             ###.......\n\
+             ..B......E\n",
+        )
+        .unwrap();
+        reveal(&mut map);
+        let robot = Robot::with_modules(2, 3, Task::Collect(ENERGY), Modules::miner());
+        let mut robots = vec![robot];
+        let mut base = Base::new();
+        base.scheduler = Scheduler::new(Box::new(FixedPolicy), 1);
+        let mut clock = Clock::default();
+
+        // Le gisement derrière le mur est le plus proche à vol d'oiseau mais hors d'atteinte :
+        // la tournée part vers celui qu'un chemin atteint
+        simulate_tick(&mut map, &mut robots, &mut base, &mut clock);
+        assert_eq!(robots[0].tour, [(9, 3)]);
+    }
+}
@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod traffic_tests {
+
+    use erea::action::{apply, Action, ActionError, Direction};
+    use erea::ascii_map::parse_ascii_map;
+    use erea::base::Base;
+    use erea::editor::reveal;
+    use erea::traffic::{Traffic, YIELD_AFTER};
+    use erea::{Robot, Task};
+
+    #[test]
+    fn test_exclusive_cells_except_base() {
+        let mut map = parse_ascii_map("B...\n").unwrap();
+        reveal(&mut map);
+        let mut base = Base::new();
+        base.traffic = Traffic::new(true);
+        let mut robots = vec![
+            Robot::new(1, 0, Task::Explore),
+            Robot::new(2, 0, Task::Explore),
+        ];
+        base.traffic.begin_tick(&map, &robots);
+
+        // La case voisine est occupée, la base accueille tout le monde
+        assert_eq!(
+            apply(
+                &mut map,
+                &mut robots[1],
+                &mut base,
+                Action::Move(Direction::West)
+            ),
+            Err(ActionError::Occupied((1, 0)))
+        );
+        apply(
+            &mut map,
+            &mut robots[0],
+            &mut base,
+            Action::Move(Direction::West),
+        )
+        .unwrap();
+        apply(
+            &mut map,
+            &mut robots[1],
+            &mut base,
+            Action::Move(Direction::West),
+        )
+        .unwrap();
+        apply(
+            &mut map,
+            &mut robots[1],
+            &mut base,
+            Action::Move(Direction::West),
+        )
+        .unwrap();
+        assert_eq!((robots[1].x, robots[1].y), map.base);
+
+        // Sans la règle, les robots se croisent librement
+        base.traffic = Traffic::new(false);
+        let mut robots = vec![
+            Robot::new(1, 0, Task::Explore),
+            Robot::new(2, 0, Task::Explore),
+        ];
+        base.traffic.begin_tick(&map, &robots);
+        apply(
+            &mut map,
+            &mut robots[1],
+            &mut base,
+            Action::Move(Direction::West),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_blocked_robot_yields() {
+        let mut map = parse_ascii_map("B...\n....\n").unwrap();
+        reveal(&mut map);
+        let mut traffic = Traffic::new(true);
+        let robots = vec![
+            Robot::new(1, 0, Task::Explore),
+            Robot::new(2, 0, Task::Explore),
+        ];
+        traffic.begin_tick(&map, &robots);
+        let mut robot = Robot::new(2, 0, Task::Explore);
+        robot.route.target = Some(map.base);
+
+        // Le robot attend, puis s'écarte vers la case libre la plus proche de sa cible
+        for _ in 0..YIELD_AFTER {
+            traffic.blocked(&map, &mut robot, (1, 0));
+        }
+        assert_eq!((robot.x, robot.y), (1, 1));
+        assert_eq!(robot.blocked, 0);
+        assert_eq!(traffic.stats.waits, YIELD_AFTER);
+        assert_eq!(traffic.stats.yields, 1);
+        assert_eq!(traffic.stats.jams_near(map.base, 1), YIELD_AFTER);
+        assert!(traffic.reserved.contains(&(1, 1)));
+        assert!(!traffic.reserved.contains(&(2, 0)));
+    }
+}
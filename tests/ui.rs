@@ -1,8 +1,9 @@
 #[cfg(test)]
 mod ui_tests {
 
-    use super::*;
-    use crate::ui::{render_ui, UiElement};
+    use erea::resources::ENERGY;
+    use erea::ui::{cell_at, draw_ui, inspect_cell, inspect_robot, render_ui, UiElement};
+    use erea::{empty_map, Robot, Task};
 
     #[test]
     fn test_ui_rendering() {
@@ -16,6 +17,64 @@ mod ui_tests {
 
         // Vérifie que le rendu de l'interface utilisateur est conforme aux attentes
         assert_eq!(rendered_ui.len(), ui_elements.len());
+    }
+
+    #[test]
+    fn test_inspector() {
+        let mut map = empty_map(10, 10);
+        map.add_deposit(ENERGY, (2, 3));
+        map.explore_all();
+        let mut robot = Robot::new(2, 3, Task::Collect(ENERGY));
+
+        // Une fenêtre de 200x200 pixels affiche 20 pixels par case
+        assert_eq!(cell_at(&map, (200, 200), (45.0, 61.0)), Some((2, 3)));
+        assert_eq!(cell_at(&map, (200, 200), (-1.0, 61.0)), None);
+
+        let cell = render_ui(&inspect_cell(
+            &map,
+            std::slice::from_ref(&robot),
+            (2, 3),
+            (0, 0),
+        ));
+        assert!(cell.contains(&"Contenu : énergie".to_string()));
+        assert!(cell.contains(&"Robots : #0 Collect(energy)".to_string()));
+
+        // Le chemin prévu suit les déplacements vers la cible
+        robot.move_towards((5, 5));
+        assert_eq!(robot.planned_path(), vec![(4, 5), (5, 5)]);
+        let panel = render_ui(&inspect_robot(&map, &robot, 0, (0, 0)));
+        assert!(panel.contains(&"Cible : (5, 5)".to_string()));
+    }
+
+    #[test]
+    fn test_panel_is_drawn_in_window() {
+        let (width, height) = (200, 40);
+        let mut buffer = vec![0; width * height];
+        draw_ui(
+            &mut buffer,
+            width,
+            &[UiElement::new("Case (2, 3)", (10, 12))],
+        );
+
+        // Le texte est écrit dans le panneau, à sa position d'ancrage
+        let pixel = |x: usize, y: usize| buffer[y * width + x];
+        assert_eq!(pixel(5, 5), 0);
+        assert_ne!(pixel(10, 12), 0);
+        assert!((12..24).any(|y| (10..80).any(|x| pixel(x, y) == 0xFF_FFFFFF)));
+        assert!(buffer[..12 * width].iter().all(|&pixel| pixel == 0));
 
+        // Un panneau qui dépasse de l'image est coupé : seul le coin visible est peint, sans
+        // déborder sur le début des lignes suivantes
+        let before = buffer.clone();
+        draw_ui(
+            &mut buffer,
+            width,
+            &[UiElement::new("Explorée : oui", (190, 35))],
+        );
+        let pixel = |x: usize, y: usize| buffer[y * width + x];
+        assert_eq!(buffer.len(), width * height);
+        assert!((190..width).all(|x| (35..height).all(|y| pixel(x, y) != 0)));
+        assert!((35..height).all(|y| (0..190).all(|x| pixel(x, y) == before[y * width + x])));
+        assert_eq!(buffer[..35 * width], before[..35 * width]);
     }
 }